@@ -1,4 +1,9 @@
-use advent_of_code::run_on_challenge_input_lines;
+use std::sync::OnceLock;
+
+use advent_of_code::{
+    run_on_challenge_input_lines,
+    utils::{TokenMode, TokenScanner},
+};
 
 fn part_one(value: impl Into<String>) -> u64 {
     let mut ret: (u64, u64) = (0, 0);
@@ -22,12 +27,69 @@ fn part_one(value: impl Into<String>) -> u64 {
     (ret.0 * 10) + ret.1
 }
 
+fn part_two(value: &str) -> u64 {
+    static SCANNER: OnceLock<TokenScanner<u64>> = OnceLock::new();
+
+    let scanner = SCANNER.get_or_init(|| {
+        let words = [
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ];
+
+        words
+            .iter()
+            .zip(1..)
+            .fold(TokenScanner::new(), |scanner, (word, digit)| {
+                scanner
+                    .literal(word, digit)
+                    .literal(&digit.to_string(), digit)
+            })
+    });
+
+    let digits = scanner.scan(value, TokenMode::Overlapping);
+
+    match (digits.first(), digits.last()) {
+        (Some(first), Some(last)) => (first.kind * 10) + last.kind,
+        _ => 0,
+    }
+}
+
 pub fn main() {
     let mut calibration_total: u64 = 0;
+    let mut corrected_total: u64 = 0;
 
     run_on_challenge_input_lines(2023, 1, |line| {
         calibration_total += part_one(line);
+        corrected_total += part_two(line);
     });
 
-    println!("{}", calibration_total);
+    println!("part one: {}", calibration_total);
+    println!("part two: {}", corrected_total);
+}
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let lines = ["1abc2", "pqr3stu8vwx", "a1b2c3d4e5f", "treb7uchet"];
+
+        assert_eq!(lines.iter().map(|line| part_one(*line)).sum::<u64>(), 142);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let lines = [
+            "two1nine",
+            "eightwothree",
+            "abcone2threexyz",
+            "xtwone3four",
+            "4nineeightseven2",
+            "zoneight234",
+            "7pqrstsixteen",
+        ];
+
+        assert_eq!(lines.iter().map(|line| part_two(line)).sum::<u64>(), 281);
+        assert_eq!(part_two("eightwo"), 82);
+    }
 }
@@ -1,7 +1,6 @@
 use std::fmt::Debug;
 
-use advent_of_code::run_on_challenge_input_lines;
-use regex::Regex;
+use advent_of_code::{run_on_challenge_input_lines, scan, scan_all};
 
 struct Game {
     pub number: u64,
@@ -22,44 +21,28 @@ impl Debug for Game {
 }
 
 fn parse_game(value: String) -> Game {
-    let regex_game_number = Regex::new(r"Game (\d+)").unwrap();
-    let regex_red = Regex::new(r"(\d+) red").unwrap();
-    let regex_green = Regex::new(r"(\d+) green").unwrap();
-    let regex_blue = Regex::new(r"(\d+) blue").unwrap();
+    let (number,) = scan!(regex r"Game (\d+)", &value => u64).unwrap();
 
-    let mut red_values: Vec<u64> = regex_red
-        .captures_iter(&value)
-        .filter_map(|capture| capture.get(1))
-        .filter_map(|m| m.as_str().parse::<u64>().ok())
-        .collect();
+    let mut red_values: Vec<u64> = vec![];
+    let mut green_values: Vec<u64> = vec![];
+    let mut blue_values: Vec<u64> = vec![];
 
-    red_values.sort_by(|a, b| b.cmp(a));
+    for captures in scan_all!(regex r"(\d+) (red|green|blue)", &value => u64, String) {
+        let (count, colour) = captures.unwrap();
 
-    let mut green_values: Vec<u64> = regex_green
-        .captures_iter(&value)
-        .filter_map(|capture| capture.get(1))
-        .filter_map(|m| m.as_str().parse::<u64>().ok())
-        .collect();
+        match colour.as_str() {
+            "red" => red_values.push(count),
+            "green" => green_values.push(count),
+            _ => blue_values.push(count),
+        }
+    }
 
+    red_values.sort_by(|a, b| b.cmp(a));
     green_values.sort_by(|a, b| b.cmp(a));
-
-    let mut blue_values: Vec<u64> = regex_blue
-        .captures_iter(&value)
-        .filter_map(|capture| capture.get(1))
-        .filter_map(|m| m.as_str().parse::<u64>().ok())
-        .collect();
-
     blue_values.sort_by(|a, b| b.cmp(a));
 
     Game {
-        number: regex_game_number
-            .captures(&value)
-            .unwrap()
-            .get(1)
-            .unwrap()
-            .as_str()
-            .parse::<u64>()
-            .unwrap(),
+        number,
         red: red_values,
         green: green_values,
         blue: blue_values,
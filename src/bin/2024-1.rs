@@ -1,55 +1,56 @@
-use advent_of_code::run_on_challenge_input_lines;
-use regex::Regex;
+use advent_of_code::{get_challenge_input_as_str, utils::columns};
 
-fn part_one() {
-    let mut left: Vec<u64> = vec![];
-    let mut right: Vec<u64> = vec![];
-
-    let regex = Regex::new(r"(\d{5})\s+(\d{5})").unwrap();
-
-    run_on_challenge_input_lines(2024, 1, |line| {
-        let captures = regex.captures(line).unwrap();
-        left.push(captures.get(1).unwrap().as_str().parse::<u64>().unwrap());
-        right.push(captures.get(2).unwrap().as_str().parse::<u64>().unwrap());
-    });
+fn parse_data(data: &str) -> Result<(Vec<u64>, Vec<u64>), String> {
+    let [mut left, mut right] = columns::<u64, 2>(data)?;
 
     left.sort();
     right.sort();
 
-    let mut total: u64 = 0;
-
-    for i in 0..left.len() {
-        total += left[i].abs_diff(right[i]);
-    }
-
-    println!("part one: {}", total);
+    Ok((left, right))
 }
 
-fn part_two() {
-    let mut left: Vec<u64> = vec![];
-    let mut right: Vec<u64> = vec![];
-
-    let regex = Regex::new(r"(\d{5})\s+(\d{5})").unwrap();
-
-    run_on_challenge_input_lines(2024, 1, |line| {
-        let captures = regex.captures(line).unwrap();
-        left.push(captures.get(1).unwrap().as_str().parse::<u64>().unwrap());
-        right.push(captures.get(2).unwrap().as_str().parse::<u64>().unwrap());
-    });
-
-    left.sort();
-    right.sort();
+fn part_one(left: &[u64], right: &[u64]) -> u64 {
+    left.iter()
+        .zip(right.iter())
+        .map(|(a, b)| a.abs_diff(*b))
+        .sum()
+}
 
-    let mut total: u64 = 0;
+fn part_two(left: &[u64], right: &[u64]) -> u64 {
+    left.iter()
+        .map(|a| a * right.iter().filter(|b| *b == a).count() as u64)
+        .sum()
+}
 
-    for i in 0..left.len() {
-        total += left[i] * right.iter().filter(|value| *value == &left[i]).count() as u64;
+pub fn main() {
+    if let Ok(data) = get_challenge_input_as_str(2024, 1) {
+        if let Ok((left, right)) = parse_data(&data) {
+            println!("part one: {}", part_one(&left, &right));
+            println!("part two: {}", part_two(&left, &right));
+        }
     }
-
-    println!("part two: {}", total);
 }
 
-pub fn main() {
-    part_one();
-    part_two();
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "3   4
+4   3
+2   5
+1   3
+3   9
+3   3";
+
+    #[test]
+    fn test_part_one() {
+        let (left, right) = parse_data(EXAMPLE_INPUT).unwrap();
+        assert_eq!(part_one(&left, &right), 11);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let (left, right) = parse_data(EXAMPLE_INPUT).unwrap();
+        assert_eq!(part_two(&left, &right), 31);
+    }
 }
@@ -1,9 +1,11 @@
 #![allow(unused)]
 use std::{collections::HashSet, fmt::Debug};
 
-use advent_of_code::{get_challenge_input_as_str, maps::Coordinate};
-use itertools::Itertools;
-use regex::Regex;
+use advent_of_code::{
+    get_challenge_input_as_str,
+    maps::Coordinate,
+    utils::{ints_n, records},
+};
 
 #[derive(Debug, Copy, Clone)]
 struct ButtonConfig {
@@ -19,66 +21,27 @@ pub struct ClawMachineConfig {
 }
 
 fn parse_data(data: &str) -> Result<Vec<ClawMachineConfig>, String> {
-    let parse_regex =
-        Regex::new(r"(Button [AB]|Prize): (X(\+[0-9]+), Y(\+[0-9]+)|X=([0-9]+), Y=([0-9]+))")
-            .unwrap();
-    let mut machine_configs: Vec<ClawMachineConfig> = Vec::new();
-
-    for block in data.lines().chunks(4).into_iter() {
-        let mut button_a: ButtonConfig = ButtonConfig {
-            x_step: 0,
-            y_step: 0,
-        };
-        let mut button_b: ButtonConfig = ButtonConfig {
-            x_step: 0,
-            y_step: 0,
-        };
-        let mut prize_location = Coordinate { x: 0i64, y: 0i64 };
-
-        for line in block {
-            if line.is_empty() {
-                continue;
-            }
-
-            if let Some(captures) = parse_regex.captures(line) {
-                if let Some(line_type) = captures.get(1) {
-                    match line_type.as_str() {
-                        "Button A" => {
-                            button_a.x_step =
-                                captures.get(3).unwrap().as_str().parse::<i64>().unwrap();
-                            button_a.y_step =
-                                captures.get(4).unwrap().as_str().parse::<i64>().unwrap();
-                        }
-                        "Button B" => {
-                            button_b.x_step =
-                                captures.get(3).unwrap().as_str().parse::<i64>().unwrap();
-                            button_b.y_step =
-                                captures.get(4).unwrap().as_str().parse::<i64>().unwrap();
-                        }
-                        "Prize" => {
-                            prize_location.x =
-                                captures.get(5).unwrap().as_str().parse::<i64>().unwrap();
-                            prize_location.y =
-                                captures.get(6).unwrap().as_str().parse::<i64>().unwrap();
-                        }
-                        _ => return Err("Line is malformed (line type incorrect)".to_string()),
-                    }
-                } else {
-                    return Err("Line is malformed (no line type)".to_string());
-                }
-            }
-        }
-
-        let machine_config = ClawMachineConfig {
-            button_a,
-            button_b,
-            prize_location,
-        };
-
-        machine_configs.push(machine_config);
-    }
-
-    Ok(machine_configs)
+    records(data)
+        .map(|record| {
+            let [a_x, a_y, b_x, b_y, prize_x, prize_y] = ints_n::<i64, 6>(record.text)
+                .map_err(|error| record.error(record.first_line, error).to_string())?;
+
+            Ok(ClawMachineConfig {
+                button_a: ButtonConfig {
+                    x_step: a_x,
+                    y_step: a_y,
+                },
+                button_b: ButtonConfig {
+                    x_step: b_x,
+                    y_step: b_y,
+                },
+                prize_location: Coordinate {
+                    x: prize_x,
+                    y: prize_y,
+                },
+            })
+        })
+        .collect()
 }
 
 fn solve_machine(config: ClawMachineConfig, part_two: bool) -> Option<i64> {
@@ -1,8 +1,12 @@
 use std::collections::{HashMap, HashSet};
 
-use advent_of_code::{get_challenge_input_as_str, maps::Coordinate};
+use advent_of_code::{
+    cycle, get_challenge_input_as_str,
+    maps::Coordinate,
+    simulation::{Runner, Simulation},
+    utils::ints_n,
+};
 use bmp::Pixel;
-use regex::Regex;
 
 #[derive(Debug)]
 struct Robot {
@@ -12,8 +16,12 @@ struct Robot {
 
 impl Robot {
     pub fn update_location(&mut self, map_width: i32, map_height: i32) -> Coordinate<i32> {
-        self.position.x = (self.position.x + self.velocity.x).rem_euclid(map_width);
-        self.position.y = (self.position.y + self.velocity.y).rem_euclid(map_height);
+        let bounds = Coordinate {
+            x: map_width,
+            y: map_height,
+        };
+
+        self.position = (self.position + self.velocity).rem_euclid(&bounds);
 
         self.position.clone()
     }
@@ -69,21 +77,16 @@ impl BathroomData {
 }
 
 fn parse_data(data: &str, width: i32, height: i32) -> BathroomData {
-    let robot_regex: Regex = Regex::new(r"p=([0-9]+),([0-9]+) v=([\-0-9]+),([\-0-9]+)").unwrap();
-
     let robots: Vec<Robot> = data
         .lines()
         .map(|line| {
-            let captures = robot_regex.captures(line).unwrap();
+            let [x, y, velocity_x, velocity_y] = ints_n::<i32, 4>(line).unwrap();
 
             return Robot {
-                position: Coordinate {
-                    x: captures.get(1).unwrap().as_str().parse::<i32>().unwrap(),
-                    y: captures.get(2).unwrap().as_str().parse::<i32>().unwrap(),
-                },
+                position: Coordinate { x, y },
                 velocity: Coordinate {
-                    x: captures.get(3).unwrap().as_str().parse::<i32>().unwrap(),
-                    y: captures.get(4).unwrap().as_str().parse::<i32>().unwrap(),
+                    x: velocity_x,
+                    y: velocity_y,
                 },
             };
         })
@@ -96,13 +99,17 @@ fn parse_data(data: &str, width: i32, height: i32) -> BathroomData {
     }
 }
 
-fn update_robot_locations(data: &mut BathroomData, steps: i32) {
-    for i in 0..steps {
-        for robot in data.robots.iter_mut() {
-            robot.update_location(data.width, data.height);
+impl Simulation for BathroomData {
+    type Frame = Vec<Coordinate<i32>>;
+
+    fn step(&mut self) {
+        for robot in self.robots.iter_mut() {
+            robot.update_location(self.width, self.height);
         }
+    }
 
-        data.render(i);
+    fn frame(&self) -> Self::Frame {
+        self.robots.iter().map(|robot| robot.position).collect()
     }
 }
 
@@ -131,30 +138,41 @@ fn calculate_safety_factor(data: &BathroomData) -> i32 {
 
 #[allow(unused)]
 fn part_one(data: &mut BathroomData) -> i32 {
-    update_robot_locations(data, 100);
+    Runner::new(data).run(100);
     calculate_safety_factor(data)
 }
 
-#[allow(unused)]
-fn part_two(data: &mut BathroomData) -> i32 {
-    let mut seconds = 0;
+fn step_positions(data: &BathroomData, positions: &[Coordinate<i32>]) -> Vec<Coordinate<i32>> {
+    let bounds = Coordinate {
+        x: data.width,
+        y: data.height,
+    };
+
+    positions
+        .iter()
+        .zip(data.robots.iter())
+        .map(|(&position, robot)| (position + robot.velocity).rem_euclid(&bounds))
+        .collect()
+}
 
-    loop {
-        let mut occupied = HashSet::new();
-        for mut robot in data.robots.iter_mut() {
-            occupied.insert(robot.update_location(data.width, data.height));
-        }
+fn looks_like_tree(positions: &[Coordinate<i32>]) -> bool {
+    let occupied: HashSet<Coordinate<i32>> = positions.iter().copied().collect();
 
-        let connected_count = occupied
-            .iter()
-            .filter(|c| c.neighbours().iter().any(|n| occupied.contains(n)))
-            .count();
-        if connected_count >= data.robots.len() / 2 {
-            return seconds;
-        }
+    let connected_count = occupied
+        .iter()
+        .filter(|c| c.neighbours().iter().any(|n| occupied.contains(n)))
+        .count();
 
-        seconds += 1;
-    }
+    connected_count >= positions.len() / 2
+}
+
+#[allow(unused)]
+fn part_two(data: &mut BathroomData) -> Option<usize> {
+    // Every arrangement the robots will ever make appears within the first
+    // mu + lambda seconds, so there is no point searching further.
+    let (mu, lambda) = cycle::brent(data.frame(), |positions| step_positions(data, positions));
+
+    Runner::new(data).run_until(|data| looks_like_tree(&data.frame()), mu + lambda)
 }
 
 pub fn main() {
@@ -163,8 +181,10 @@ pub fn main() {
 
         // let result = part_one(&mut data);
         // println!("part one: {}", result);
-        let result = part_two(&mut data);
-        println!("part two: {}", result);
+        if let Some(result) = part_two(&mut data) {
+            println!("part two: {}", result);
+            data.render(result as i32);
+        }
     }
 }
 
@@ -199,12 +219,21 @@ p=9,5 v=-3,-3";
     #[test]
     fn test_part_one() {
         let mut data = parse_data(EXAMPLE_INPUT, 11, 7);
-        update_robot_locations(&mut data, 100);
+        Runner::new(&mut data).run(100);
         let safety_factor = calculate_safety_factor(&data);
 
         assert_eq!(safety_factor, 12);
     }
 
     #[test]
-    fn test_part_two() {}
+    fn test_part_two() {
+        let data = parse_data(EXAMPLE_INPUT, 11, 7);
+        let initial: Vec<Coordinate<i32>> =
+            data.robots.iter().map(|robot| robot.position).collect();
+
+        assert_eq!(
+            cycle::brent(initial, |positions| step_positions(&data, positions)),
+            (0, 77)
+        );
+    }
 }
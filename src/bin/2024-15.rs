@@ -1,347 +1,192 @@
-use std::{
-    collections::HashSet,
-    fmt::{Debug, Display},
-};
+use std::fmt::Display;
 
 use advent_of_code::{
     get_challenge_input_as_str,
-    maps::{Coordinate, Direction, Direction::*},
+    maps::{Coordinate, Direction, Direction::*, EntityGrid, EntityId, MapData, SparseGrid},
+    simulation::{Runner, Simulation},
+    utils::{parse_sections, Section},
 };
 
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Tile {
+    Wall,
+    Box,
+    Robot,
+}
+
 struct WarehouseData {
-    width: usize,
-    height: usize,
-    robot_location: Coordinate<i32>,
-    boxes: HashSet<Coordinate<i32>>,
-    walls: HashSet<Coordinate<i32>>,
+    warehouse: EntityGrid<Tile>,
+    robot: EntityId,
     robot_moves: Vec<Direction>,
+    next_move: usize,
 }
 
-impl Debug for WarehouseData {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut lines: Vec<String> = Vec::new();
-
-        for y in 0..self.height {
-            let mut x = 0;
-            let mut line: Vec<char> = Vec::new();
-
-            while x < self.width {
-                let coordinate = Coordinate {
-                    x: x as i32,
-                    y: y as i32,
-                };
-                match coordinate {
-                    _ if self.walls.contains(&coordinate) => line.push('#'),
-                    _ if self.boxes.contains(&coordinate) => {
-                        line.push('[');
-                        line.push(']');
-                        x += 1;
-                    }
-                    _ if self.robot_location == coordinate => line.push('@'),
-                    _ => line.push('.'),
-                }
-
-                x += 1
-            }
-
-            lines.push(line.iter().collect::<String>());
-        }
+impl WarehouseData {
+    #[allow(unused)]
+    fn robot_location(&self) -> Coordinate<i32> {
+        self.warehouse.entity(self.robot).position
+    }
 
-        write!(f, "{}", lines.join("\n"))
+    fn boxes(&self) -> Vec<Coordinate<i32>> {
+        self.warehouse
+            .entities()
+            .filter(|(_, entity)| entity.kind == Tile::Box)
+            .map(|(_, entity)| entity.position)
+            .collect()
     }
 }
 
 impl Display for WarehouseData {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut lines: Vec<String> = Vec::new();
-
-        for y in 0..self.height {
-            let mut line: Vec<char> = Vec::new();
-
-            for x in 0..self.width {
-                let coordinate = Coordinate {
-                    x: x as i32,
-                    y: y as i32,
-                };
-                match coordinate {
-                    _ if self.walls.contains(&coordinate) => line.push('#'),
-                    _ if self.boxes.contains(&coordinate) => line.push('O'),
-                    _ if self.robot_location == coordinate => line.push('@'),
-                    _ => line.push('.'),
-                }
-            }
-
-            lines.push(line.iter().collect::<String>());
-        }
-
-        write!(f, "{}", lines.join("\n"))
-    }
-}
-
-fn parse_data(data: &str, part_two: bool) -> WarehouseData {
-    let mut robot_location: Coordinate<i32> = Coordinate { x: 0, y: 0 };
-    let mut boxes: HashSet<Coordinate<i32>> = HashSet::new();
-    let mut walls: HashSet<Coordinate<i32>> = HashSet::new();
-    let mut robot_moves: Vec<Direction> = vec![];
-    let mut width: usize = 0;
-    let mut height: usize = 0;
-
-    data.lines().enumerate().for_each(|(row, line)| {
-        let mut line = line.to_string();
-
-        if line.is_empty() {
-            return;
-        };
+        let mut tiles: SparseGrid<Option<EntityId>> = SparseGrid::new(None);
 
-        if line.starts_with('#') {
-            if part_two {
-                line = line
-                    .split("")
-                    .map(|chunk| match chunk {
-                        "@" => "@.".to_string(),
-                        "O" => "[]".to_string(),
-                        _ => chunk.repeat(2),
-                    })
-                    .collect::<String>();
+        for (id, entity) in self.warehouse.entities() {
+            for cell in entity.cells() {
+                tiles.insert(cell, Some(id));
             }
+        }
 
-            width = line.len();
-            height += 1;
+        let rendered = tiles.render(|coordinate, tile| match tile {
+            Some(id) => {
+                let entity = self.warehouse.entity(id);
 
-            for (column, character) in line.chars().enumerate() {
-                match character {
-                    '#' => {
-                        walls.insert(Coordinate {
-                            x: column as i32,
-                            y: row as i32,
-                        });
-                    }
-                    'O' | '[' => {
-                        boxes.insert(Coordinate {
-                            x: column as i32,
-                            y: row as i32,
-                        });
-                    }
-                    '@' => {
-                        robot_location = Coordinate {
-                            x: column as i32,
-                            y: row as i32,
-                        };
-                    }
-                    _ => {}
-                };
+                match entity.kind {
+                    Tile::Wall => '#',
+                    Tile::Robot => '@',
+                    Tile::Box if entity.shape.len() == 1 => 'O',
+                    Tile::Box if entity.position == coordinate => '[',
+                    Tile::Box => ']',
+                }
             }
-        }
+            None => '.',
+        });
 
-        robot_moves.extend(
-            line.chars()
-                .map(|c| match c {
-                    '<' => Some(West),
-                    '>' => Some(East),
-                    '^' => Some(North),
-                    'v' => Some(South),
-                    _ => None,
-                })
-                .filter(Option::is_some)
-                .map(Option::unwrap)
-                .collect::<Vec<Direction>>(),
-        );
-    });
-
-    WarehouseData {
-        width,
-        height,
-        robot_location,
-        boxes,
-        walls,
-        robot_moves,
+        write!(f, "{}", rendered)
     }
 }
 
-fn can_move_box(
-    from: Coordinate<i32>,
-    direction: Direction,
-    data: &mut WarehouseData,
-    part_two: bool,
-) -> bool {
-    match from.neighbour(direction) {
-        coordinate if data.walls.contains(&coordinate) => {
-            return false;
-        }
-        coordinate if part_two && direction == West => {
-            if data.boxes.contains(&coordinate.neighbour(West)) {
-                if can_move_box(coordinate.neighbour(West), direction, data, part_two) {
-                    data.boxes.remove(&coordinate.neighbour(West));
-                    data.boxes
-                        .insert(coordinate.neighbour(West).neighbour(West));
-
-                    return true;
-                }
-                return false;
-            }
+impl Simulation for WarehouseData {
+    type Frame = String;
 
-            return true;
+    fn step(&mut self) {
+        if let Some(&direction) = self.robot_moves.get(self.next_move) {
+            self.warehouse.push(self.robot, direction);
+            self.next_move += 1;
         }
-        coordinate if part_two && direction == North => {
-            if data.boxes.contains(&coordinate.neighbour(NorthWest)) {
-                if can_move_box(coordinate.neighbour(NorthWest), direction, data, part_two) {
-                    data.boxes.remove(&coordinate.neighbour(NorthWest));
-                    data.boxes
-                        .insert(coordinate.neighbour(NorthWest).neighbour(North));
-                } else {
-                    return false;
-                }
-            }
-            if data.boxes.contains(&coordinate.neighbour(North)) {
-                if can_move_box(coordinate.neighbour(North), direction, data, part_two) {
-                    data.boxes.remove(&coordinate.neighbour(North));
-                    data.boxes
-                        .insert(coordinate.neighbour(North).neighbour(North));
-                } else {
-                    return false;
-                }
-            }
-            if data.boxes.contains(&coordinate.neighbour(NorthEast)) {
-                if can_move_box(coordinate.neighbour(NorthEast), direction, data, part_two) {
-                    data.boxes.remove(&coordinate.neighbour(NorthEast));
-                    data.boxes
-                        .insert(coordinate.neighbour(NorthEast).neighbour(North));
-                } else {
-                    return false;
-                }
-            }
-            return true;
-        }
-        coordinate if data.boxes.contains(&coordinate) => {
-            if can_move_box(coordinate, direction, data, part_two) {
-                data.boxes.remove(&coordinate);
-                data.boxes.insert(
-                    coordinate
-                        + match direction {
-                            North => Coordinate { x: 0, y: -1 },
-                            East => Coordinate { x: 1, y: 0 },
-                            South => Coordinate { x: 0, y: 1 },
-                            West => Coordinate { x: -1, y: 0 },
-                            _ => unreachable!(),
-                        },
-                );
-                return true;
-            }
+    }
 
-            return false;
-        }
-        _ => {
-            return true;
-        }
+    fn frame(&self) -> Self::Frame {
+        self.to_string()
     }
 }
 
-fn can_make_move(
-    from: Coordinate<i32>,
-    direction: Direction,
-    data: &mut WarehouseData,
-    part_two: bool,
-) -> bool {
-    match from.neighbour(direction) {
-        coordinate if data.walls.contains(&coordinate) => {
-            return false;
-        }
-        coordinate if part_two && direction == West => {
-            if data.boxes.contains(&coordinate.neighbour(West)) {
-                if can_move_box(coordinate.neighbour(West), direction, data, part_two) {
-                    data.boxes.remove(&coordinate.neighbour(West));
-                    data.boxes
-                        .insert(coordinate.neighbour(West).neighbour(West));
-
-                    return true;
-                }
-                return false;
-            }
+fn parse_move(c: char) -> Result<Direction, String> {
+    match c {
+        '<' => Ok(West),
+        '>' => Ok(East),
+        '^' => Ok(North),
+        'v' => Ok(South),
+        _ => Err(format!("Invalid robot move '{c}'")),
+    }
+}
 
-            return true;
-        }
-        coordinate if part_two && direction == North => {
-            if data.boxes.contains(&coordinate.neighbour(NorthWest)) {
-                if can_move_box(coordinate.neighbour(NorthWest), direction, data, part_two) {
-                    data.boxes.remove(&coordinate.neighbour(NorthWest));
-                    data.boxes
-                        .insert(coordinate.neighbour(NorthWest).neighbour(North));
-                } else {
-                    return false;
-                }
-            }
+fn parse_data(data: &str, part_two: bool) -> Result<WarehouseData, String> {
+    let mut warehouse: EntityGrid<Tile> = EntityGrid::new();
 
-            if data.boxes.contains(&coordinate.neighbour(North)) {
-                if can_move_box(coordinate.neighbour(North), direction, data, part_two) {
-                    data.boxes.remove(&coordinate.neighbour(North));
-                    data.boxes
-                        .insert(coordinate.neighbour(North).neighbour(North));
-                } else {
-                    return false;
-                }
-            }
+    let (lines, robot_moves) = parse_sections(
+        data,
+        (
+            |section: Section| {
+                section.parse_lines(|line| {
+                    if !part_two {
+                        return Ok::<_, String>(line.to_string());
+                    }
 
-            return true;
-        }
-        coordinate if data.boxes.contains(&coordinate) => {
-            if can_move_box(coordinate, direction, data, part_two) {
-                data.boxes.remove(&coordinate);
-                data.boxes.insert(
-                    coordinate
-                        + match direction {
-                            North => Coordinate { x: 0, y: -1 },
-                            East => Coordinate { x: 1, y: 0 },
-                            South => Coordinate { x: 0, y: 1 },
-                            West => Coordinate { x: -1, y: 0 },
-                            _ => unreachable!(),
-                        },
-                );
-                return true;
-            }
+                    Ok(line
+                        .split("")
+                        .map(|chunk| match chunk {
+                            "@" => "@.".to_string(),
+                            "O" => "[]".to_string(),
+                            _ => chunk.repeat(2),
+                        })
+                        .collect::<String>())
+                })
+            },
+            |section: Section| {
+                section
+                    .parse_lines(|line| line.chars().map(parse_move).collect::<Result<Vec<_>, _>>())
+            },
+        ),
+    )
+    .map_err(|error| error.to_string())?;
+
+    let (grid, markers) = MapData::<char>::parse_with_markers(&lines.join("\n"), &['@'], '.')
+        .map_err(|error| error.to_string())?;
+
+    let single = vec![Coordinate { x: 0, y: 0 }];
+    let wide = vec![Coordinate { x: 0, y: 0 }, Coordinate { x: 1, y: 0 }];
+
+    for (coordinate, &character) in grid.enumerate::<usize>() {
+        let position = Coordinate {
+            x: coordinate.x as i32,
+            y: coordinate.y as i32,
+        };
 
-            return false;
-        }
-        _ => {
-            return true;
-        }
+        match character {
+            '#' => warehouse.add(Tile::Wall, position, single.clone(), false),
+            'O' => warehouse.add(Tile::Box, position, single.clone(), true),
+            '[' => warehouse.add(Tile::Box, position, wide.clone(), true),
+            _ => continue,
+        }?;
     }
-}
 
-fn solve(data: &mut WarehouseData, part_two: bool) -> i32 {
-    for (index, m) in data.robot_moves.clone().iter().enumerate() {
-        if can_make_move(data.robot_location, *m, data, part_two) {
-            data.robot_location += match m {
-                North => Coordinate { x: 0, y: -1 },
-                East => Coordinate { x: 1, y: 0 },
-                South => Coordinate { x: 0, y: 1 },
-                West => Coordinate { x: -1, y: 0 },
-                _ => unreachable!(),
-            }
-        }
+    let robot = markers[&'@'];
+    let robot = warehouse.add(
+        Tile::Robot,
+        Coordinate {
+            x: robot.x as i32,
+            y: robot.y as i32,
+        },
+        single,
+        true,
+    )?;
+
+    Ok(WarehouseData {
+        warehouse,
+        robot,
+        robot_moves: robot_moves.concat(),
+        next_move: 0,
+    })
+}
 
-        if part_two {
-            println!("move {}: {:?}\n{:?}\n\n", index + 1, m, data);
-        }
-    }
+fn solve(data: &mut WarehouseData) -> i32 {
+    let moves = data.robot_moves.len();
+    Runner::new(data).run(moves);
 
-    data.boxes.iter().map(|b| (b.y * 100 + b.x) as i32).sum()
+    data.boxes().iter().map(|b| b.y * 100 + b.x).sum()
 }
 
 #[allow(unused)]
 fn part_one(data: &mut WarehouseData) -> i32 {
-    solve(data, false)
+    solve(data)
 }
 
 #[allow(unused)]
 fn part_two(data: &mut WarehouseData) -> i32 {
-    solve(data, true)
+    solve(data)
 }
 
 pub fn main() {
-    if let Ok(data) = get_challenge_input_as_str(2024, 15) {
-        let mut data = parse_data(&data, false);
-        let result = part_one(&mut data);
-        println!("part one: {}", result);
+    if let Ok(input) = get_challenge_input_as_str(2024, 15) {
+        match parse_data(&input, false) {
+            Ok(mut data) => println!("part one: {}", part_one(&mut data)),
+            Err(error) => println!("{error}"),
+        }
+
+        match parse_data(&input, true) {
+            Ok(mut data) => println!("part two: {}", part_two(&mut data)),
+            Err(error) => println!("{error}"),
+        }
     }
 }
 
@@ -394,29 +239,57 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
 
     #[test]
     fn test_parse_part_one() {
-        let data = parse_data(EXAMPLE_INPUT, false);
+        let data = parse_data(EXAMPLE_INPUT, false).unwrap();
 
-        assert_eq!(data.boxes.len(), 6);
-        assert_eq!(data.robot_location, Coordinate { x: 2, y: 2 });
+        assert_eq!(data.boxes().len(), 6);
+        assert_eq!(data.robot_location(), Coordinate { x: 2, y: 2 });
         assert_eq!(data.robot_moves.len(), 15);
+        assert_eq!(
+            data.to_string(),
+            EXAMPLE_INPUT.split("\n\n").next().unwrap()
+        );
     }
 
     #[test]
     fn test_parse_part_two() {
-        let data = parse_data(LARGER_EXAMPLE, true);
+        let data = parse_data(LARGER_EXAMPLE, true).unwrap();
 
         println!("{}", data);
     }
 
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_data("#..#\n\n<", false).is_err_and(|error| error.contains("'@'")));
+        assert!(parse_data("#@@#\n\n<", false).is_err_and(|error| error.contains("more than once")));
+        assert!(parse_data("#@.#\n\n<x", false).is_err());
+    }
+
+    #[test]
+    fn test_replay() {
+        let mut data = parse_data(PART_TWO_EXAMPLE, true).unwrap();
+        let initial = data.to_string();
+
+        let mut runner = Runner::new(&mut data).with_history(20);
+        runner.run(11);
+
+        let mut replay = runner.history().unwrap().replay();
+        assert_eq!(replay.current(), Some((0, &initial)));
+
+        let (tick, last) = replay.to_end().unwrap();
+        assert_eq!(tick, 11);
+        assert!(last.starts_with("##############\n##...[].##..##\n##...@.[]...##"));
+        assert_ne!(replay.backward().unwrap().1, last);
+    }
+
     #[test]
     fn test_part_one() {
-        let mut data = parse_data(EXAMPLE_INPUT, false);
+        let mut data = parse_data(EXAMPLE_INPUT, false).unwrap();
 
         let result = part_one(&mut data);
 
         assert_eq!(result, 2028);
 
-        let mut data = parse_data(LARGER_EXAMPLE, false);
+        let mut data = parse_data(LARGER_EXAMPLE, false).unwrap();
 
         let result = part_one(&mut data);
 
@@ -425,7 +298,13 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^";
 
     #[test]
     fn test_part_two() {
-        let mut data = parse_data(PART_TWO_EXAMPLE, true);
+        let mut data = parse_data(PART_TWO_EXAMPLE, true).unwrap();
+
+        let result = part_two(&mut data);
+
+        assert_eq!(result, 618);
+
+        let mut data = parse_data(LARGER_EXAMPLE, true).unwrap();
 
         let result = part_two(&mut data);
 
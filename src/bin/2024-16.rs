@@ -1,11 +1,9 @@
-use std::{
-    collections::{BinaryHeap, HashMap, HashSet},
-    fmt::Display,
-};
+use std::{collections::HashSet, fmt::Display};
 
 use advent_of_code::{
     get_challenge_input_as_str,
-    maps::{Coordinate, Direction},
+    maps::{Coordinate, Direction, MapData, SparseGrid},
+    search::{dijkstra, SearchResult},
 };
 
 struct Maze {
@@ -13,193 +11,109 @@ struct Maze {
     height: i32,
     start: Coordinate<i32>,
     end: Coordinate<i32>,
-    walls: HashSet<Coordinate<i32>>,
+    walls: SparseGrid<bool>,
 }
 
 impl Display for Maze {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut lines: Vec<String> = Vec::new();
-
-        for y in 0..self.height {
-            let mut x = 0;
-            let mut line: Vec<char> = Vec::new();
-
-            while x < self.width {
-                let coordinate = Coordinate {
-                    x: x as i32,
-                    y: y as i32,
-                };
-                match coordinate {
-                    _ if self.walls.contains(&coordinate) => line.push('#'),
-                    _ if self.start == coordinate => line.push('S'),
-                    _ if self.end == coordinate => line.push('E'),
-                    _ => line.push('.'),
-                }
-
-                x += 1
-            }
-
-            lines.push(line.iter().collect::<String>());
-        }
+        let corner = Coordinate {
+            x: self.width - 1,
+            y: self.height - 1,
+        };
+
+        let rendered =
+            self.walls
+                .render_within(Coordinate { x: 0, y: 0 }, corner, |coordinate, wall| {
+                    match coordinate {
+                        _ if wall => '#',
+                        _ if self.start == coordinate => 'S',
+                        _ if self.end == coordinate => 'E',
+                        _ => '.',
+                    }
+                });
 
-        write!(f, "{}", lines.join("\n"))
+        write!(f, "{}", rendered)
     }
 }
 
 fn parse_data(data: &str) -> Result<Maze, String> {
-    let width = data
-        .lines()
-        .next()
-        .ok_or_else(|| "input data did not contain any lines".to_string())?
-        .len() as i32;
-
-    if !data.lines().all(|line| line.len() as i32 == width) {
-        return Err(format!(
-            "expected all lines to have the same length ({width})"
-        ));
-    }
-
-    let height = data.lines().count() as i32;
-
-    let mut start: Coordinate<i32> = Coordinate { x: 0, y: 0 };
-    let mut end: Coordinate<i32> = Coordinate { x: 0, y: 0 };
-    let mut walls: HashSet<Coordinate<i32>> = HashSet::new();
-
-    for (y, line) in data.lines().enumerate() {
-        for (x, character) in line.chars().enumerate() {
-            match character {
-                '#' => {
-                    walls.insert(Coordinate {
-                        x: x as i32,
-                        y: y as i32,
-                    });
-                }
-                'S' => {
-                    start = Coordinate {
-                        x: x as i32,
-                        y: y as i32,
-                    };
-                }
-                'E' => {
-                    end = Coordinate {
-                        x: x as i32,
-                        y: y as i32,
-                    };
-                }
-                _ => {}
-            }
+    let (map, markers) = MapData::<char>::parse_with_markers(data, &['S', 'E'], '.')
+        .map_err(|error| error.to_string())?;
+
+    let mut walls: SparseGrid<bool> = SparseGrid::new(false);
+
+    for (coordinate, &character) in map.enumerate::<usize>() {
+        if character == '#' {
+            walls.insert(
+                Coordinate {
+                    x: coordinate.x as i32,
+                    y: coordinate.y as i32,
+                },
+                true,
+            );
         }
     }
 
+    let to_maze_coordinate = |coordinate: Coordinate<usize>| Coordinate {
+        x: coordinate.x as i32,
+        y: coordinate.y as i32,
+    };
+
     Ok(Maze {
-        width,
-        height,
-        start,
-        end,
+        width: map.width as i32,
+        height: map.height as i32,
+        start: to_maze_coordinate(markers[&'S']),
+        end: to_maze_coordinate(markers[&'E']),
         walls,
     })
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-struct MazeNode {
-    coordinate: Coordinate<i32>,
-    cost: i32,
-    direction: Direction,
-}
-
-impl Ord for MazeNode {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.cost.cmp(&self.cost)
-    }
-}
-
-impl PartialOrd for MazeNode {
-    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
-        Some(self.cmp(other))
-    }
-}
-
-fn solve(maze: Maze) -> i32 {
-    use Direction::*;
-
-    let mut frontier: BinaryHeap<MazeNode> = BinaryHeap::new();
-    frontier.push(MazeNode {
-        coordinate: maze.start,
-        cost: 0,
-        direction: East,
-    });
-
-    let mut came_from: HashMap<Coordinate<i32>, Option<Coordinate<i32>>> = HashMap::new();
-    let mut cost_so_far: HashMap<Coordinate<i32>, i32> = HashMap::new();
-
-    came_from.insert(maze.start, None);
-    cost_so_far.insert(maze.start, 0);
+type Reindeer = (Coordinate<i32>, Direction);
 
-    let mut score: i32 = std::i32::MAX;
+fn solve(maze: &Maze) -> Option<SearchResult<Reindeer, i32>> {
+    dijkstra(
+        (maze.start, Direction::East),
+        |&(coordinate, direction): &Reindeer| {
+            let mut moves = vec![
+                ((coordinate, direction.turn_left()), 1000),
+                ((coordinate, direction.turn_right()), 1000),
+            ];
 
-    while !frontier.is_empty() {
-        let current = frontier.pop().unwrap();
-
-        if current.coordinate == maze.end {
-            let current_score = *cost_so_far.get(&current.coordinate).unwrap();
-
-            score = if score > current_score {
-                current_score
-            } else {
-                score
-            };
-        }
-
-        for &neighbour in current.coordinate.neighbours().iter() {
-            if maze.walls.contains(&neighbour) {
-                continue;
-            }
-
-            let new_direction = current.coordinate.direction_to(&neighbour).unwrap();
-
-            let new_cost = cost_so_far.get(&current.coordinate).unwrap_or(&0)
-                + if new_direction != current.direction {
-                    1001
-                } else {
-                    1
-                };
-
-            if !cost_so_far.contains_key(&neighbour)
-                || new_cost < *cost_so_far.get(&neighbour).unwrap()
-            {
-                cost_so_far.insert(neighbour, new_cost);
-
-                frontier.push(MazeNode {
-                    coordinate: neighbour,
-                    cost: new_cost,
-                    direction: new_direction,
-                });
-
-                came_from.insert(neighbour, Some(current.coordinate));
+            let ahead = coordinate + direction.offset();
+            if !maze.walls.contains(&ahead) {
+                moves.push(((ahead, direction), 1));
             }
-        }
-    }
 
-    score
+            moves
+        },
+        |&(coordinate, _)| coordinate == maze.end,
+    )
 }
 
 #[allow(unused)]
-fn part_one(maze: Maze) -> i32 {
-    solve(maze)
+fn part_one(maze: &Maze) -> i32 {
+    solve(maze).map_or(0, |result| result.cost)
 }
 
 #[allow(unused)]
-fn part_two(data: &str) -> i32 {
-    0
+fn part_two(maze: &Maze) -> usize {
+    solve(maze).map_or(0, |result| {
+        result
+            .optimal_states()
+            .into_iter()
+            .map(|(coordinate, _)| coordinate)
+            .collect::<HashSet<_>>()
+            .len()
+    })
 }
 
 pub fn main() {
     if let Ok(data) = get_challenge_input_as_str(2024, 16) {
         if let Ok(data) = parse_data(&data) {
-            let result = part_one(data);
+            let result = part_one(&data);
             println!("part one: {}", result);
-            // let result = part_two(&data);
-            // println!("part two: {}", result);
+            let result = part_two(&data);
+            println!("part two: {}", result);
         }
     }
 }
@@ -301,26 +215,34 @@ mod tests {
     #[test]
     fn test_part_one() {
         if let Ok(data) = parse_data(EXAMPLE_INPUT) {
-            assert_eq!(solve(data), 7036);
+            assert_eq!(part_one(&data), 7036);
         }
 
         if let Ok(data) = parse_data(SECOND_EXAMPLE) {
-            assert_eq!(solve(data), 11048);
+            assert_eq!(part_one(&data), 11048);
         }
 
         if let Ok(data) = parse_data(ALTERNATE) {
-            assert_eq!(solve(data), 21148);
+            assert_eq!(part_one(&data), 21148);
         }
 
         if let Ok(data) = parse_data(LONGER_ALTERNATE) {
-            assert_eq!(solve(data), 41210);
+            assert_eq!(part_one(&data), 41210);
         }
 
         if let Ok(data) = parse_data(ANOTHER_ALTERNATE) {
-            assert_eq!(solve(data), 4013);
+            assert_eq!(part_one(&data), 4013);
         }
     }
 
     #[test]
-    fn test_part_two() {}
+    fn test_part_two() {
+        if let Ok(data) = parse_data(EXAMPLE_INPUT) {
+            assert_eq!(part_two(&data), 45);
+        }
+
+        if let Ok(data) = parse_data(SECOND_EXAMPLE) {
+            assert_eq!(part_two(&data), 64);
+        }
+    }
 }
@@ -1,52 +1,77 @@
-use std::collections::HashSet;
+use advent_of_code::{
+    get_challenge_input_as_str,
+    maps::{Coordinate, MapData, SparseGrid},
+};
 
-use advent_of_code::{get_challenge_input_as_str, maps::Coordinate};
+fn parse_bytes(data: &str) -> Result<Vec<Coordinate<i32>>, String> {
+    data.lines().map(str::parse).collect()
+}
 
-#[allow(unused)]
-fn part_one(data: &str, line_limit: usize, memory_space: usize) -> i32 {
-    let mut bytes: HashSet<Coordinate<i32>> = HashSet::new();
-    let mut lines = data.lines();
-
-    for i in 0..line_limit {
-        if let Some(current_line) = lines.next() {
-            bytes.insert(current_line.parse().unwrap());
-        } else {
-            break;
-        }
+fn memory_after(bytes: &[Coordinate<i32>], memory_space: usize) -> MapData<char> {
+    let mut corrupted: SparseGrid<char> = SparseGrid::new('.');
+
+    for &byte in bytes {
+        corrupted.insert(byte, '#');
     }
 
-    let mut render_lines: Vec<String> = vec![];
+    let corner = Coordinate {
+        x: memory_space as i32,
+        y: memory_space as i32,
+    };
 
-    for y in 0..=memory_space {
-        let mut current_line: Vec<char> = vec![];
-        for x in 0..=memory_space {
-            if bytes.contains(&Coordinate { x: x as i32, y: y as i32 }) {
-                current_line.push('#');
-            } else {
-                current_line.push('.');
-            }
-        }
-
-        render_lines.push(current_line.iter().collect::<String>());
-    }
+    corrupted.to_map_data_within(Coordinate { x: 0, y: 0 }, corner)
+}
 
-    println!("{}", render_lines.join("\n"));
+#[allow(unused)]
+fn part_one(data: &str, line_limit: usize, memory_space: usize) -> u32 {
+    let bytes = parse_bytes(data).unwrap();
+    let memory = memory_after(&bytes[..line_limit.min(bytes.len())], memory_space);
 
-    
+    let distances = memory.bfs_distances(&Coordinate { x: 0usize, y: 0 }, |cell| cell == '.');
+    let exit = Coordinate {
+        x: memory_space,
+        y: memory_space,
+    };
 
-    0
+    distances.get(&exit).flatten().unwrap_or(0)
 }
 
 #[allow(unused)]
-fn part_two(data: &str) -> i32 {
-    0
+fn part_two(data: &str, memory_space: usize) -> String {
+    let bytes = parse_bytes(data).unwrap();
+    let mut memory = memory_after(&bytes, memory_space);
+
+    let is_open = |cell: char| cell == '.';
+    let mut regions = memory.disjoint_set(is_open);
+
+    let start = memory.cell_index(&Coordinate { x: 0, y: 0 });
+    let exit = memory.cell_index(&Coordinate {
+        x: memory_space,
+        y: memory_space,
+    });
+
+    for byte in bytes.iter().rev() {
+        let cell = Coordinate {
+            x: byte.x as usize,
+            y: byte.y as usize,
+        };
+
+        memory.set(&cell, '.');
+        memory.union_neighbours(&mut regions, &cell, is_open);
+
+        if regions.connected(start, exit) {
+            return format!("{},{}", byte.x, byte.y);
+        }
+    }
+
+    String::new()
 }
 
 pub fn main() {
     if let Ok(data) = get_challenge_input_as_str(2024, 18) {
         let result = part_one(&data, 1024, 70);
         println!("part one: {}", result);
-        let result = part_two(&data);
+        let result = part_two(&data, 70);
         println!("part two: {}", result);
     }
 }
@@ -83,9 +108,11 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        part_one(EXAMPLE_INPUT, 12, 6);
+        assert_eq!(part_one(EXAMPLE_INPUT, 12, 6), 22);
     }
 
     #[test]
-    fn test_part_two() {}
+    fn test_part_two() {
+        assert_eq!(part_two(EXAMPLE_INPUT, 6), "6,1");
+    }
 }
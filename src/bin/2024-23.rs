@@ -1,68 +1,40 @@
-use std::collections::{HashMap, HashSet};
+use advent_of_code::{get_challenge_input_as_str, graph::Graph};
 
-use advent_of_code::get_challenge_input_as_str;
-
-#[allow(unused)]
-fn part_one(data: &str) -> i32 {
-    let mut all_connections: HashMap<String, HashSet<String>> = HashMap::new();
-    let mut interconnected: i32 = 0;
-
-    for line in data.lines() {
-        let computer_a = &line[0..2];
-        let computer_b = &line[3..5];
-
-        all_connections
-            .entry(computer_a.to_string())
-            .or_insert_with(HashSet::new)
-            .insert(computer_b.to_string());
-
-        all_connections
-            .entry(computer_b.to_string())
-            .or_insert_with(HashSet::new)
-            .insert(computer_a.to_string());
-    }
-
-    let mut triples: Vec<HashSet<String>> = Vec::new();
-
-    for (computer, connected_computers) in &all_connections {
-        let connected_computers: Vec<_> = connected_computers.iter().collect();
-        for i in 0..connected_computers.len() {
-            for j in i + 1..connected_computers.len() {
-                let computer_a = connected_computers[i];
-                let computer_b = connected_computers[j];
-
-                if all_connections
-                    .get(computer_a)
-                    .map_or(false, |n| n.contains(computer_b))
-                {
-                    let triple: HashSet<String> =
-                        HashSet::from([computer.clone(), computer_a.clone(), computer_b.clone()]);
-
-                    if !triples.contains(&triple) {
-                        triples.push(triple);
-                    }
-                }
-            }
-        }
-    }
-
-    triples
+fn part_one(network: &Graph) -> usize {
+    network
+        .triangles()
         .iter()
-        .filter(|t| t.iter().any(|c| c.starts_with("t")))
-        .count() as i32
+        .filter(|triangle| {
+            triangle
+                .iter()
+                .any(|&computer| network.name(computer).starts_with('t'))
+        })
+        .count()
 }
 
-#[allow(unused)]
-fn part_two(data: &str) -> i32 {
-    0
+/**
+ * The LAN party is the largest set of fully connected computers
+ */
+fn part_two(network: &Graph) -> String {
+    let mut party: Vec<&str> = network
+        .maximum_clique()
+        .into_iter()
+        .map(|computer| network.name(computer))
+        .collect();
+    party.sort();
+
+    party.join(",")
 }
 
 pub fn main() {
     if let Ok(data) = get_challenge_input_as_str(2024, 23) {
-        let result = part_one(&data);
-        println!("part one: {}", result);
-        let result = part_two(&data);
-        println!("part two: {}", result);
+        match data.parse::<Graph>() {
+            Ok(network) => {
+                println!("part one: {}", part_one(&network));
+                println!("part two: {}", part_two(&network));
+            }
+            Err(error) => println!("{error}"),
+        }
     }
 }
 
@@ -105,9 +77,13 @@ td-yn";
 
     #[test]
     fn test_part_one() {
-        assert_eq!(part_one(EXAMPLE_INPUT), 7);
+        let network: Graph = EXAMPLE_INPUT.parse().unwrap();
+        assert_eq!(part_one(&network), 7);
     }
 
     #[test]
-    fn test_part_two() {}
+    fn test_part_two() {
+        let network: Graph = EXAMPLE_INPUT.parse().unwrap();
+        assert_eq!(part_two(&network), "co,de,ka,ta");
+    }
 }
@@ -1,142 +1,82 @@
-use std::{
-    collections::{HashMap, HashSet},
-    fmt::Display,
-    str::FromStr,
-};
+use std::collections::HashMap;
 
-use advent_of_code::get_challenge_input_as_str;
+use advent_of_code::{
+    circuit::{read_bus, Circuit},
+    get_challenge_input_as_str,
+    utils::{parse_sections, Section},
+};
 
-#[derive(Debug, Hash, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
-enum Operation {
-    AND,
-    OR,
-    XOR,
+struct Device {
+    inputs: HashMap<String, bool>,
+    circuit: Circuit,
 }
 
-#[derive(Hash, PartialEq, Eq, PartialOrd, Ord, Clone)]
-struct Gate {
-    input_a: String,
-    operation: Operation,
-    input_b: String,
-    wire: String,
+fn parse_data(data: &str) -> Result<Device, String> {
+    let (inputs, circuit) = parse_sections(
+        data,
+        (
+            |section: Section| {
+                section.parse_lines(|line| {
+                    let (wire, value) = line
+                        .split_once(": ")
+                        .ok_or_else(|| format!("Invalid wire value: '{line}'"))?;
+
+                    match value {
+                        "0" => Ok((wire.to_string(), false)),
+                        "1" => Ok((wire.to_string(), true)),
+                        _ => Err(format!("Invalid wire value: '{line}'")),
+                    }
+                })
+            },
+            |section: Section| section.parse(str::parse::<Circuit>),
+        ),
+    )
+    .map_err(|error| error.to_string())?;
+
+    Ok(Device {
+        inputs: inputs.into_iter().collect(),
+        circuit,
+    })
 }
 
-impl FromStr for Gate {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut parts = s.split(" ");
+fn part_one(device: &Device) -> Result<u64, String> {
+    let wires = device
+        .circuit
+        .evaluate(&device.inputs)
+        .map_err(|error| error.to_string())?;
 
-        let input_a = parts.next().expect("Error parsing input A").to_string();
-        let operation = match parts.next().expect("Invalid operation") {
-            "AND" => Ok(Operation::AND),
-            "OR" => Ok(Operation::OR),
-            "XOR" => Ok(Operation::XOR),
-            _ => Err("Invalid parsed value"),
-        }
-        .expect("Error parsing operation");
-        let input_b = parts.next().expect("Error parsing input B").to_string();
-
-        let _ = parts.next();
-        let wire = parts.next().expect("Error parsing wire").to_string();
-
-        Ok(Gate {
-            input_a,
-            operation,
-            input_b,
-            wire,
-        })
-    }
+    read_bus(&wires, "z")
 }
 
-impl Display for Gate {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(
-            f,
-            "input a: {}, operation: {:?}, input b: {} -> {}",
-            self.input_a, self.operation, self.input_b, self.wire
-        )
-    }
-}
-
-#[allow(unused)]
-fn part_one(data: &str) -> i64 {
-    let mut wires: HashMap<String, i64> = HashMap::new();
-    let mut gates: HashSet<Gate> = HashSet::new();
-
-    let mut lines = data.lines();
-    let mut current_line = lines.next();
-
-    while current_line.is_some_and(|l| !l.is_empty()) {
-        let line = current_line.unwrap();
-        let wire = &line[0..3];
-        let value: i64 = line[5..].parse().unwrap();
-
-        wires.insert(wire.to_string(), value);
-
-        current_line = lines.next();
-    }
-
-    current_line = lines.next();
-
-    while current_line.is_some() {
-        let gate: Gate = current_line.unwrap().parse().expect("Error parsing gate");
-        gates.insert(gate);
-
-        current_line = lines.next();
-    }
-
-    while !gates.is_empty() {
-        let mut calculated: Vec<Gate> = Vec::new();
-
-        for gate in &gates {
-            let input_a = wires.get(&gate.input_a);
-            let input_b = wires.get(&gate.input_b);
-
-            if input_a.is_some() && input_b.is_some() {
-                use Operation::*;
-
-                wires.insert(
-                    gate.wire.clone(),
-                    match gate.operation {
-                        AND => input_a.unwrap() & input_b.unwrap(),
-                        OR => input_a.unwrap() | input_b.unwrap(),
-                        XOR => input_a.unwrap() ^ input_b.unwrap(),
-                    },
-                );
-
-                calculated.push(gate.clone());
-            }
-        }
-
-        for gate in calculated {
-            gates.remove(&gate);
-        }
-    }
-
-    let mut filtered: Vec<(&String, &i64)> =
-        wires.iter().filter(|(k, v)| k.starts_with('z')).collect();
-    filtered.sort_by(|(a, _), (b, _)| b.cmp(a));
-
-    let mut binary_string = String::new();
-    filtered
-        .iter()
-        .for_each(|(_, &v)| binary_string.push_str(&v.to_string()));
-
-    i64::from_str_radix(&binary_string, 2).unwrap()
-}
-
-#[allow(unused)]
-fn part_two(data: &str) -> i32 {
-    0
+/**
+ * The device should be an adder, so the swapped wires are the outputs of the
+ * gates that don't fit the shape of one
+ */
+fn part_two(device: &Device) -> String {
+    let mut wires: Vec<&str> = device
+        .circuit
+        .adder_faults()
+        .into_iter()
+        .map(|gate| gate.output.as_str())
+        .collect();
+    wires.sort();
+
+    wires.join(",")
 }
 
 pub fn main() {
     if let Ok(data) = get_challenge_input_as_str(2024, 24) {
-        let result = part_one(&data);
-        println!("part one: {}", result);
-        let result = part_two(&data);
-        println!("part two: {}", result);
+        match parse_data(&data) {
+            Ok(device) => {
+                match part_one(&device) {
+                    Ok(result) => println!("part one: {}", result),
+                    Err(error) => println!("{error}"),
+                }
+
+                println!("part two: {}", part_two(&device));
+            }
+            Err(error) => println!("{error}"),
+        }
     }
 }
 
@@ -192,11 +132,36 @@ hwm AND bqk -> z03
 tgd XOR rvg -> z12
 tnw OR pbm -> gnj";
 
+    /// A three bit adder with the outputs of `z01` and `b01` swapped.
+    const ADDER_INPUT: &str = "x00: 1
+x01: 1
+x02: 0
+y00: 1
+y01: 0
+y02: 1
+
+x00 XOR y00 -> z00
+x00 AND y00 -> c00
+x01 XOR y01 -> s01
+s01 XOR c00 -> b01
+x01 AND y01 -> a01
+s01 AND c00 -> z01
+a01 OR b01 -> c01
+x02 XOR y02 -> s02
+s02 XOR c01 -> z02
+x02 AND y02 -> a02
+s02 AND c01 -> b02
+a02 OR b02 -> z03";
+
     #[test]
     fn test_part_one() {
-        assert_eq!(part_one(EXAMPLE_INPUT), 2024);
+        let device = parse_data(EXAMPLE_INPUT).unwrap();
+        assert_eq!(part_one(&device), Ok(2024));
     }
 
     #[test]
-    fn test_part_two() {}
+    fn test_part_two() {
+        let device = parse_data(ADDER_INPUT).unwrap();
+        assert_eq!(part_two(&device), "b01,z01");
+    }
 }
@@ -1,32 +1,45 @@
-use advent_of_code::{run_on_challenge_input_lines, utils::split_keep};
-use regex::Regex;
+use std::sync::OnceLock;
 
-fn sum_uncorrupted_instructions(line: &str) -> u64 {
-    let re = Regex::new(r"mul\(([0-9]{1,3})\,([0-9]{1,3})\)").unwrap();
+use advent_of_code::{
+    run_on_challenge_input_lines, scan_all,
+    utils::{TokenMode, TokenScanner},
+};
 
-    let total: u64 = re
-        .captures_iter(&line)
-        .map(|c| {
-            c.get(1).unwrap().as_str().parse::<u64>().unwrap()
-                * c.get(2).unwrap().as_str().parse::<u64>().unwrap()
+fn sum_uncorrupted_instructions(line: &str) -> u64 {
+    scan_all!(regex r"mul\(([0-9]{1,3}),([0-9]{1,3})\)", line => u64, u64)
+        .map(|captures| {
+            let (a, b) = captures.unwrap();
+            a * b
         })
-        .sum();
+        .sum()
+}
 
-    total
+#[derive(Clone, Copy)]
+enum Instruction {
+    Do,
+    Dont,
+    Mul,
 }
 
 fn sum_uncorrupted_enabled_instructions(line: &str, enabled: &mut bool) -> u64 {
-    let re = Regex::new(r"don\'t\(\)|do\(\)").unwrap();
+    static SCANNER: OnceLock<TokenScanner<Instruction>> = OnceLock::new();
+
+    let scanner = SCANNER.get_or_init(|| {
+        TokenScanner::new()
+            .literal("do()", Instruction::Do)
+            .literal("don't()", Instruction::Dont)
+            .pattern(r"mul\([0-9]{1,3},[0-9]{1,3}\)", Instruction::Mul)
+    });
 
     let mut total: u64 = 0;
 
-    for m in split_keep(&re, line) {
-        match m {
-            "don't()" => *enabled = false,
-            "do()" => *enabled = true,
-            _ => {
+    for token in scanner.scan(line, TokenMode::LeftmostLongest) {
+        match token.kind {
+            Instruction::Do => *enabled = true,
+            Instruction::Dont => *enabled = false,
+            Instruction::Mul => {
                 if *enabled {
-                    total += sum_uncorrupted_instructions(m)
+                    total += sum_uncorrupted_instructions(token.text)
                 }
             }
         }
@@ -60,3 +73,23 @@ pub fn main() {
     part_one();
     part_two();
 }
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let line = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
+        assert_eq!(sum_uncorrupted_instructions(line), 161);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let line = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        let mut enabled = true;
+
+        assert_eq!(sum_uncorrupted_enabled_instructions(line, &mut enabled), 48);
+        assert!(enabled);
+    }
+}
@@ -1,117 +1,130 @@
-use std::{cell::Cell, collections::HashMap};
+use advent_of_code::{
+    get_challenge_input_as_str,
+    ordering::Precedence,
+    utils::{parse_sections, Section},
+};
 
-use advent_of_code::run_on_challenge_input_lines;
+struct PrintQueue {
+    rules: Precedence<u64>,
+    updates: Vec<Vec<u64>>,
+}
+
+fn parse_data(data: &str) -> Result<PrintQueue, String> {
+    let (rules, updates) = parse_sections(
+        data,
+        (
+            |section: Section| section.parse(str::parse::<Precedence<u64>>),
+            |section: Section| {
+                section.parse_lines(|line| {
+                    line.split(',')
+                        .map(|page| page.trim().parse::<u64>().map_err(|e| e.to_string()))
+                        .collect::<Result<Vec<u64>, String>>()
+                })
+            },
+        ),
+    )
+    .map_err(|error| error.to_string())?;
 
-enum ParseState {
-    OrderingRules,
-    UpdatePages,
+    Ok(PrintQueue { rules, updates })
 }
 
-fn is_update_valid(numbers: &Vec<u64>, ordering_rules: &HashMap<u64, Vec<u64>>) -> bool {
-    for i in 0..numbers.len() {
-        if let Some(rules) = ordering_rules.get(&numbers[i]) {
-            if numbers[..i].iter().any(|n| rules.contains(n)) {
-                return false;
-            }
-        }
-    }
+fn middle_page(update: &[u64]) -> u64 {
+    update[update.len() / 2]
+}
 
-    true
+fn part_one(queue: &PrintQueue) -> u64 {
+    queue
+        .updates
+        .iter()
+        .filter(|update| queue.rules.is_ordered(update))
+        .map(|update| middle_page(update))
+        .sum()
 }
 
-fn part_one() {
-    let mut ordering_rules: HashMap<u64, Vec<u64>> = HashMap::new();
-    let mut current_state = ParseState::OrderingRules;
-    let mut total: u64 = 0;
-
-    run_on_challenge_input_lines(2024, 5, |line| {
-        if line.is_empty() {
-            current_state = ParseState::UpdatePages;
-        } else {
-            match current_state {
-                ParseState::OrderingRules => {
-                    if let &Ok(first_page) = &line[0..2].parse::<u64>() {
-                        if let &Ok(second_page) = &line[3..5].parse::<u64>() {
-                            ordering_rules
-                                .entry(first_page)
-                                .or_insert_with(Vec::new)
-                                .push(second_page);
-                        }
-                    }
-                }
-                ParseState::UpdatePages => {
-                    let page_numbers: Vec<u64> = line
-                        .split(",")
-                        .map(|n| n.parse::<u64>().expect("Error parsing number from string"))
-                        .collect();
-
-                    if is_update_valid(&page_numbers, &ordering_rules) {
-                        total += page_numbers[page_numbers.len() / 2];
-                    }
-                }
-            }
+fn part_two(queue: &PrintQueue) -> Result<u64, String> {
+    let mut total = 0;
+
+    for update in &queue.updates {
+        if !queue.rules.is_ordered(update) {
+            let sorted = queue
+                .rules
+                .sort_subset(update)
+                .map_err(|error| error.to_string())?;
+
+            total += middle_page(&sorted);
         }
-    });
+    }
 
-    println!("part one: {}", total);
+    Ok(total)
 }
 
-fn part_two() {
-    let mut ordering_rules: HashMap<u64, Vec<u64>> = HashMap::new();
-    let mut current_state = ParseState::OrderingRules;
-    let mut total: u64 = 0;
-
-    run_on_challenge_input_lines(2024, 5, |line| {
-        if line.is_empty() {
-            current_state = ParseState::UpdatePages;
-        } else {
-            match current_state {
-                ParseState::OrderingRules => {
-                    if let &Ok(first_page) = &line[0..2].parse::<u64>() {
-                        if let &Ok(second_page) = &line[3..5].parse::<u64>() {
-                            ordering_rules
-                                .entry(first_page)
-                                .or_insert_with(Vec::new)
-                                .push(second_page);
-                        }
-                    }
-                }
-                ParseState::UpdatePages => {
-                    let mut page_numbers: Vec<u64> = line
-                        .split(",")
-                        .map(|n| n.parse::<u64>().expect("Error parsing number from string"))
-                        .collect();
-
-                    if !is_update_valid(&page_numbers, &ordering_rules) {
-                        let page_numbers_slice = &mut page_numbers[..];
-                        let slice_of_cells: &[Cell<u64>] =
-                            Cell::from_mut(page_numbers_slice).as_slice_of_cells();
-
-                        while !is_update_valid(
-                            &slice_of_cells.iter().map(|c| c.get()).collect::<Vec<u64>>(),
-                            &ordering_rules,
-                        ) {
-                            for w in slice_of_cells.windows(2) {
-                                if let Some(rules) = ordering_rules.get(&w[1].get()) {
-                                    if rules.contains(&w[0].get()) {
-                                        Cell::swap(&w[0], &w[1]);
-                                    }
-                                }
-                            }
-                        }
-
-                        page_numbers = slice_of_cells.iter().map(|c| c.get()).collect::<Vec<u64>>();
-                        total += page_numbers[page_numbers.len() / 2];
-                    }
+pub fn main() {
+    if let Ok(data) = get_challenge_input_as_str(2024, 5) {
+        match parse_data(&data) {
+            Ok(queue) => {
+                println!("part one: {}", part_one(&queue));
+
+                match part_two(&queue) {
+                    Ok(result) => println!("part two: {}", result),
+                    Err(error) => println!("{error}"),
                 }
             }
+            Err(error) => println!("{error}"),
         }
-    });
-
-    println!("part two: {}", total);
+    }
 }
 
-pub fn main() {
-    part_one();
-    part_two();
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47";
+
+    #[test]
+    fn test_part_one() {
+        let queue = parse_data(EXAMPLE_INPUT).unwrap();
+        assert_eq!(part_one(&queue), 143);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let queue = parse_data(EXAMPLE_INPUT).unwrap();
+        assert_eq!(part_two(&queue), Ok(123));
+    }
+
+    #[test]
+    fn test_comparator() {
+        let queue = parse_data(EXAMPLE_INPUT).unwrap();
+
+        let mut update = vec![97, 13, 75, 29, 47];
+        update.sort_by(|a, b| queue.rules.compare(a, b));
+        assert_eq!(update, vec![97, 75, 47, 29, 13]);
+    }
 }
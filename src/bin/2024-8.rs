@@ -1,12 +1,18 @@
 use std::collections::{HashMap, HashSet};
 
-use advent_of_code::{get_challenge_input_as_str, run_on_challenge_input_lines};
+use advent_of_code::{get_challenge_input_as_str, maps::Coordinate};
 use itertools::Itertools;
 
 struct Map {
     width: i32,
     height: i32,
-    antenna_locations: HashMap<char, Vec<(f32, f32)>>,
+    antenna_locations: HashMap<char, Vec<Coordinate<i32>>>,
+}
+
+impl Map {
+    fn contains(&self, coordinate: &Coordinate<i32>) -> bool {
+        (0..self.width).contains(&coordinate.x) && (0..self.height).contains(&coordinate.y)
+    }
 }
 
 fn parse_input(data: &str) -> Result<Map, String> {
@@ -22,14 +28,22 @@ fn parse_input(data: &str) -> Result<Map, String> {
         ));
     }
 
-    let antenna_locations: HashMap<char, Vec<(f32, f32)>> = data
+    let antenna_locations: HashMap<char, Vec<Coordinate<i32>>> = data
         .lines()
         .enumerate()
         .flat_map(|(row, line)| {
             line.chars()
                 .enumerate()
                 .filter(|(_, character)| *character != '.')
-                .map(move |(column, character)| (character, (column as f32, row as f32)))
+                .map(move |(column, character)| {
+                    (
+                        character,
+                        Coordinate {
+                            x: column as i32,
+                            y: row as i32,
+                        },
+                    )
+                })
         })
         .fold(HashMap::new(), |mut map, (character, coordinate)| {
             map.entry(character)
@@ -48,50 +62,26 @@ fn parse_input(data: &str) -> Result<Map, String> {
     })
 }
 
-fn distance_between_points(point_a: &(f32, f32), point_b: &(f32, f32)) -> f32 {
-    ((point_b.0 - point_a.0).powi(2) + (point_b.1 - point_b.1).powi(2)).sqrt()
-}
-
 #[allow(unused)]
 fn part_one(data: &str) -> i32 {
-    let mut total_antinodes: HashSet<(i32, i32)> = HashSet::new();
+    let mut total_antinodes: HashSet<Coordinate<i32>> = HashSet::new();
 
     if let Ok(map) = parse_input(data) {
         for (frequency, positions) in map.antenna_locations.iter() {
             for pair in positions.iter().combinations(2) {
-                let antenna_a_position = pair[0];
-                let antenna_b_position = pair[1];
+                let antenna_a_position = *pair[0];
+                let antenna_b_position = *pair[1];
 
-                let distance = distance_between_points(antenna_a_position, antenna_b_position);
-                let direction_x = (antenna_b_position.0 - antenna_a_position.0) / distance;
-                let direction_y = (antenna_b_position.1 - antenna_a_position.1) / distance;
+                let offset = antenna_b_position - antenna_a_position;
 
-                let antinode_a = (
-                    (antenna_a_position.0 - direction_x * distance) as i32,
-                    (antenna_a_position.1 - direction_y * distance) as i32,
-                );
-
-                let antinode_b = (
-                    (antenna_b_position.0 + direction_x * distance) as i32,
-                    (antenna_b_position.1 + direction_y * distance) as i32,
-                );
-
-                total_antinodes.insert(antinode_a);
-                total_antinodes.insert(antinode_b);
+                total_antinodes.insert(antenna_a_position - offset);
+                total_antinodes.insert(antenna_b_position + offset);
             }
         }
 
-        println!(
-            "{:?}",
-            total_antinodes
-                .iter()
-                .filter(|(x, y)| (0..map.width).contains(x) && (0..map.height).contains(y))
-                .collect::<Vec<_>>()
-        );
-
         return total_antinodes
             .iter()
-            .filter(|(x, y)| (0..map.width).contains(x) && (0..map.height).contains(y))
+            .filter(|antinode| map.contains(antinode))
             .count() as i32;
     }
 
@@ -100,53 +90,34 @@ fn part_one(data: &str) -> i32 {
 
 #[allow(unused)]
 fn part_two(data: &str) -> i32 {
-    let mut total_antinodes: HashSet<(i32, i32)> = HashSet::new();
+    let mut total_antinodes: HashSet<Coordinate<i32>> = HashSet::new();
 
     if let Ok(map) = parse_input(data) {
         for (frequency, positions) in map.antenna_locations.iter() {
             for pair in positions.iter().combinations(2) {
-                let antenna_a_position = pair[0];
-                let antenna_b_position = pair[1];
-
-                let row_diff = antenna_a_position.0 - antenna_b_position.0;
-                let col_diff = antenna_a_position.1 - antenna_b_position.1;
-
-                // total_antinodes.insert((antenna_a_position.0 as i32, antenna_a_position.1 as i32));
-                // total_antinodes.insert((antenna_b_position.0 as i32, antenna_b_position.1 as i32));
-
-                // let distance = distance_between_points(antenna_a_position, antenna_b_position);
-                // let direction_x = (antenna_b_position.0 - antenna_a_position.0) / distance;
-                // let direction_y = (antenna_b_position.1 - antenna_a_position.1) / distance;
-
-                for d in 1..map.height {
-                    let antinode_a = (
-                        (antenna_a_position.0 - row_diff * (d as f32)) as i32,
-                        (antenna_a_position.1 - col_diff * (d as f32)) as i32,
-                    );
-
-                    let antinode_b = (
-                        (antenna_b_position.0 + row_diff * (d as f32)) as i32,
-                        (antenna_b_position.1 + col_diff * (d as f32)) as i32,
-                    );
+                let antenna_a_position = *pair[0];
+                let antenna_b_position = *pair[1];
+
+                let Some(step) = antenna_a_position.reduced_direction_to(&antenna_b_position)
+                else {
+                    continue;
+                };
+
+                let mut antinode = antenna_a_position;
+                while map.contains(&antinode) {
+                    total_antinodes.insert(antinode);
+                    antinode += step;
+                }
 
-                    total_antinodes.insert(antinode_a);
-                    total_antinodes.insert(antinode_b);
+                let mut antinode = antenna_a_position - step;
+                while map.contains(&antinode) {
+                    total_antinodes.insert(antinode);
+                    antinode -= step;
                 }
             }
         }
 
-        println!(
-            "{:?}",
-            total_antinodes
-                .iter()
-                .filter(|(x, y)| (0..map.width).contains(x) && (0..map.height).contains(y))
-                .collect::<Vec<_>>()
-        );
-
-        return total_antinodes
-            .iter()
-            .filter(|(x, y)| (0..map.width).contains(x) && (0..map.height).contains(y))
-            .count() as i32;
+        return total_antinodes.len() as i32;
     }
 
     return 0;
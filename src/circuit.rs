@@ -0,0 +1,426 @@
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    str::FromStr,
+};
+
+use crate::ordering::Precedence;
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone, Copy)]
+pub enum Operation {
+    And,
+    Or,
+    Xor,
+}
+
+impl FromStr for Operation {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "AND" => Ok(Operation::And),
+            "OR" => Ok(Operation::Or),
+            "XOR" => Ok(Operation::Xor),
+            _ => Err(format!("Invalid operation '{s}'")),
+        }
+    }
+}
+
+impl Operation {
+    pub fn apply(self, a: bool, b: bool) -> bool {
+        match self {
+            Operation::And => a & b,
+            Operation::Or => a | b,
+            Operation::Xor => a ^ b,
+        }
+    }
+}
+
+#[derive(Debug, Hash, PartialEq, Eq, Clone)]
+pub struct Gate {
+    pub inputs: [String; 2],
+    pub operation: Operation,
+    pub output: String,
+}
+
+impl FromStr for Gate {
+    type Err = String;
+
+    /// Parses a gate written as `a AND b -> c`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split_whitespace().collect();
+
+        match parts[..] {
+            [a, operation, b, "->", output] => Ok(Gate {
+                inputs: [a.to_string(), b.to_string()],
+                operation: operation.parse()?,
+                output: output.to_string(),
+            }),
+            _ => Err(format!("Invalid gate '{s}'")),
+        }
+    }
+}
+
+impl Gate {
+    fn has_input(&self, wire: &str) -> bool {
+        self.inputs.iter().any(|input| input == wire)
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum CircuitError {
+    /// Two gates drive the same wire.
+    MultipleDrivers(String),
+    /// The gates feed back into themselves through these wires, in order.
+    Cycle(Vec<String>),
+    /// A gate reads a wire that no gate drives and that was not given a
+    /// value.
+    Undriven(String),
+}
+
+impl Display for CircuitError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CircuitError::MultipleDrivers(wire) => {
+                write!(f, "Wire '{wire}' is driven by more than one gate")
+            }
+            CircuitError::Cycle(wires) => write!(f, "Gates form a cycle: {}", wires.join(" -> ")),
+            CircuitError::Undriven(wire) => write!(f, "Wire '{wire}' has no value"),
+        }
+    }
+}
+
+impl std::error::Error for CircuitError {}
+
+/// A network of logic gates, kept in an order where every gate comes after
+/// the gates driving its inputs.
+#[derive(Debug, Clone)]
+pub struct Circuit {
+    gates: Vec<Gate>,
+}
+
+impl FromStr for Circuit {
+    type Err = String;
+
+    /// Parses one gate per line.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let gates = s
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(str::parse::<Gate>)
+            .collect::<Result<Vec<Gate>, String>>()?;
+
+        Circuit::new(gates).map_err(|error| error.to_string())
+    }
+}
+
+impl Circuit {
+    pub fn new(gates: Vec<Gate>) -> Result<Self, CircuitError> {
+        let mut drivers: HashMap<&str, usize> = HashMap::new();
+
+        for (index, gate) in gates.iter().enumerate() {
+            if drivers.insert(&gate.output, index).is_some() {
+                return Err(CircuitError::MultipleDrivers(gate.output.clone()));
+            }
+        }
+
+        let mut rules: Precedence<usize> = Precedence::new();
+
+        for (index, gate) in gates.iter().enumerate() {
+            for input in &gate.inputs {
+                if let Some(&driver) = drivers.get(input.as_str()) {
+                    rules.add(driver, index);
+                }
+            }
+        }
+
+        let order: Vec<usize> = (0..gates.len()).collect();
+        let order = rules.sort_subset(&order).map_err(|error| {
+            CircuitError::Cycle(
+                error
+                    .cycle
+                    .into_iter()
+                    .map(|index| gates[index].output.clone())
+                    .collect(),
+            )
+        })?;
+
+        let mut gates: Vec<Option<Gate>> = gates.into_iter().map(Some).collect();
+
+        Ok(Self {
+            gates: order
+                .into_iter()
+                .map(|index| gates[index].take().unwrap())
+                .collect(),
+        })
+    }
+
+    /// The gates in evaluation order.
+    pub fn gates(&self) -> &[Gate] {
+        &self.gates
+    }
+
+    pub fn driver(&self, wire: &str) -> Option<&Gate> {
+        self.gates.iter().find(|gate| gate.output == wire)
+    }
+
+    /// A copy of this circuit with the outputs of two gates exchanged.
+    pub fn with_swapped(&self, a: &str, b: &str) -> Result<Self, CircuitError> {
+        let gates = self
+            .gates
+            .iter()
+            .cloned()
+            .map(|mut gate| {
+                if gate.output == a {
+                    gate.output = b.to_string();
+                } else if gate.output == b {
+                    gate.output = a.to_string();
+                }
+
+                gate
+            })
+            .collect();
+
+        Circuit::new(gates)
+    }
+
+    /// Every wire read by a gate but not driven by one, sorted by name.
+    pub fn inputs(&self) -> Vec<&str> {
+        let outputs: HashSet<&str> = self.gates.iter().map(|gate| gate.output.as_str()).collect();
+
+        let mut inputs: Vec<&str> = self
+            .gates
+            .iter()
+            .flat_map(|gate| gate.inputs.iter().map(String::as_str))
+            .filter(|wire| !outputs.contains(wire))
+            .collect::<HashSet<&str>>()
+            .into_iter()
+            .collect();
+        inputs.sort();
+
+        inputs
+    }
+
+    /// The value of every wire, given the values of the input wires.
+    pub fn evaluate(
+        &self,
+        inputs: &HashMap<String, bool>,
+    ) -> Result<HashMap<String, bool>, CircuitError> {
+        let mut wires = inputs.clone();
+
+        for gate in &self.gates {
+            let [a, b] = &gate.inputs;
+            let value = |wire: &String| {
+                wires
+                    .get(wire)
+                    .copied()
+                    .ok_or_else(|| CircuitError::Undriven(wire.clone()))
+            };
+
+            let output = gate.operation.apply(value(a)?, value(b)?);
+            wires.insert(gate.output.clone(), output);
+        }
+
+        Ok(wires)
+    }
+
+    /// Checks the gates against the shape of a ripple-carry adder summing
+    /// the `x` and `y` buses into the `z` bus, and lists every gate whose
+    /// output looks to be wired to the wrong place:
+    ///
+    /// - every `z` bit but the last comes from an XOR, and the last (the
+    ///   final carry) from an OR
+    /// - an XOR of two carry-chain wires must drive a `z` bit
+    /// - an XOR of `x` and `y` bits, other than bit 0, must feed another XOR
+    /// - an AND, other than of bit 0, must feed an OR
+    pub fn adder_faults(&self) -> Vec<&Gate> {
+        let last_z = self
+            .gates
+            .iter()
+            .map(|gate| &gate.output)
+            .filter(|wire| wire.starts_with('z'))
+            .max();
+
+        let is_bus_input = |wire: &str| wire.starts_with('x') || wire.starts_with('y');
+        let is_bit_zero = |gate: &Gate| {
+            gate.inputs
+                .iter()
+                .all(|wire| is_bus_input(wire) && wire.get(1..) == Some("00"))
+        };
+        let feeds = |gate: &Gate, operation: Operation| {
+            self.gates
+                .iter()
+                .any(|next| next.operation == operation && next.has_input(&gate.output))
+        };
+
+        self.gates
+            .iter()
+            .filter(|gate| {
+                let direct = gate.inputs.iter().all(|wire| is_bus_input(wire));
+
+                if gate.output.starts_with('z') {
+                    let expected = if Some(&gate.output) == last_z {
+                        Operation::Or
+                    } else {
+                        Operation::Xor
+                    };
+
+                    if gate.operation != expected {
+                        return true;
+                    }
+                }
+
+                match gate.operation {
+                    Operation::Xor if !direct => !gate.output.starts_with('z'),
+                    Operation::Xor => !is_bit_zero(gate) && !feeds(gate, Operation::Xor),
+                    Operation::And => !is_bit_zero(gate) && !feeds(gate, Operation::Or),
+                    Operation::Or => false,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Reads the wires named `prefix` followed by a bit number, such as `z00`
+/// to `z45`, as a binary number. Fails if a set bit does not fit in a `u64`.
+pub fn read_bus(wires: &HashMap<String, bool>, prefix: &str) -> Result<u64, String> {
+    let mut total: u64 = 0;
+
+    for (wire, &value) in wires {
+        let Some(bit) = wire
+            .strip_prefix(prefix)
+            .and_then(|bit| bit.parse::<u32>().ok())
+        else {
+            continue;
+        };
+
+        if value {
+            total |= 1u64
+                .checked_shl(bit)
+                .ok_or_else(|| format!("Wire '{wire}' is too wide for a 64 bit bus"))?;
+        }
+    }
+
+    Ok(total)
+}
+
+/// Sets the wires `prefix00` to the given width from the bits of `value`.
+pub fn write_bus(wires: &mut HashMap<String, bool>, prefix: &str, value: u64, width: usize) {
+    for bit in 0..width {
+        let set = value
+            .checked_shr(bit as u32)
+            .is_some_and(|shifted| shifted & 1 == 1);
+        wires.insert(format!("{prefix}{bit:02}"), set);
+    }
+}
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    /// A ripple-carry adder for two `bits` wide numbers.
+    fn adder(bits: usize) -> String {
+        let mut gates = vec![
+            "x00 XOR y00 -> z00".to_string(),
+            "x00 AND y00 -> c00".to_string(),
+        ];
+
+        for bit in 1..bits {
+            let carry = format!("c{:02}", bit - 1);
+            let out = if bit == bits - 1 {
+                format!("z{bits:02}")
+            } else {
+                format!("c{bit:02}")
+            };
+
+            gates.push(format!("x{bit:02} XOR y{bit:02} -> s{bit:02}"));
+            gates.push(format!("s{bit:02} XOR {carry} -> z{bit:02}"));
+            gates.push(format!("x{bit:02} AND y{bit:02} -> a{bit:02}"));
+            gates.push(format!("s{bit:02} AND {carry} -> b{bit:02}"));
+            gates.push(format!("a{bit:02} OR b{bit:02} -> {out}"));
+        }
+
+        gates.join("\n")
+    }
+
+    fn add(circuit: &Circuit, x: u64, y: u64, bits: usize) -> Result<u64, CircuitError> {
+        let mut inputs = HashMap::new();
+        write_bus(&mut inputs, "x", x, bits);
+        write_bus(&mut inputs, "y", y, bits);
+
+        Ok(read_bus(&circuit.evaluate(&inputs)?, "z").unwrap())
+    }
+
+    #[test]
+    fn test_buses() {
+        let mut wires = HashMap::new();
+        write_bus(&mut wires, "x", u64::MAX, 70);
+
+        assert_eq!(wires.len(), 70);
+        assert!(wires["x63"]);
+        assert!(!wires["x64"]);
+        assert_eq!(read_bus(&wires, "x"), Ok(u64::MAX));
+        assert_eq!(read_bus(&wires, "y"), Ok(0));
+
+        wires.insert("x64".to_string(), true);
+        assert!(read_bus(&wires, "x").is_err());
+    }
+
+    #[test]
+    fn test_parse() {
+        let gate: Gate = "ntg XOR fgs -> mjb".parse().unwrap();
+        assert_eq!(gate.operation, Operation::Xor);
+        assert_eq!(gate.inputs, ["ntg".to_string(), "fgs".to_string()]);
+
+        assert!("a NAND b -> c".parse::<Gate>().is_err());
+        assert!("a AND b c".parse::<Gate>().is_err());
+        assert!("a AND b -> c\nd OR e -> c".parse::<Circuit>().is_err());
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let circuit: Circuit = adder(4).parse().unwrap();
+
+        assert_eq!(circuit.gates()[0].output, "z00");
+        assert_eq!(circuit.inputs().len(), 8);
+        assert_eq!(add(&circuit, 11, 6, 4), Ok(17));
+        assert_eq!(add(&circuit, 15, 15, 4), Ok(30));
+
+        assert_eq!(
+            circuit.evaluate(&HashMap::new()),
+            Err(CircuitError::Undriven("x00".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_cycle() {
+        let circuit: Result<Circuit, CircuitError> = Circuit::new(vec![
+            "x00 AND b -> a".parse().unwrap(),
+            "a OR y00 -> b".parse().unwrap(),
+            "a XOR b -> z00".parse().unwrap(),
+        ]);
+
+        assert_eq!(
+            circuit.unwrap_err(),
+            CircuitError::Cycle(vec!["a".to_string(), "b".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_adder_faults() {
+        let circuit: Circuit = adder(6).parse().unwrap();
+        assert!(circuit.adder_faults().is_empty());
+
+        let swapped = circuit.with_swapped("z03", "a03").unwrap();
+        assert_ne!(add(&swapped, 9, 1, 6), Ok(10));
+
+        let mut faults: Vec<&str> = swapped
+            .adder_faults()
+            .iter()
+            .map(|gate| gate.output.as_str())
+            .collect();
+        faults.sort();
+        assert_eq!(faults, vec!["a03", "z03"]);
+    }
+}
@@ -0,0 +1,217 @@
+use std::{collections::HashMap, hash::Hash};
+
+/// Finds the cycle in the sequence of states produced by repeatedly calling
+/// `step`, returning `(mu, lambda)`: the number of steps before the cycle
+/// starts and the length of the cycle. Loops forever if no state repeats.
+///
+/// Uses Brent's algorithm, which keeps only two states in memory and calls
+/// `step` fewer times than `floyd`.
+pub fn brent<S, F>(initial: S, step: F) -> (usize, usize)
+where
+    S: PartialEq + Clone,
+    F: FnMut(&S) -> S,
+{
+    brent_within(initial, step, usize::MAX).unwrap()
+}
+
+/// Like `brent`, but gives up and returns `None` if no repeat is found
+/// within `limit` steps of the search.
+pub fn brent_within<S, F>(initial: S, mut step: F, limit: usize) -> Option<(usize, usize)>
+where
+    S: PartialEq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let mut power = 1;
+    let mut lambda = 1;
+    let mut steps = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+
+    while tortoise != hare {
+        if steps >= limit {
+            return None;
+        }
+
+        if power == lambda {
+            tortoise = hare.clone();
+            power *= 2;
+            lambda = 0;
+        }
+
+        hare = step(&hare);
+        lambda += 1;
+        steps += 1;
+    }
+
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..lambda {
+        hare = step(&hare);
+    }
+
+    let mut mu = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+
+    Some((mu, lambda))
+}
+
+/// Like `brent`, using Floyd's tortoise and hare algorithm.
+pub fn floyd<S, F>(initial: S, step: F) -> (usize, usize)
+where
+    S: PartialEq + Clone,
+    F: FnMut(&S) -> S,
+{
+    floyd_within(initial, step, usize::MAX).unwrap()
+}
+
+/// Like `floyd`, but gives up and returns `None` if no repeat is found
+/// within `limit` steps of the tortoise.
+pub fn floyd_within<S, F>(initial: S, mut step: F, limit: usize) -> Option<(usize, usize)>
+where
+    S: PartialEq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let mut steps = 1;
+    let mut tortoise = step(&initial);
+    let mut hare = step(&tortoise);
+
+    while tortoise != hare {
+        if steps >= limit {
+            return None;
+        }
+
+        steps += 1;
+        tortoise = step(&tortoise);
+        let halfway = step(&hare);
+        hare = step(&halfway);
+    }
+
+    let mut mu = 0;
+    let mut tortoise = initial;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+
+    let mut lambda = 1;
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        lambda += 1;
+    }
+
+    Some((mu, lambda))
+}
+
+/// Like `brent`, but remembers every state seen so `step` is called exactly
+/// `mu + lambda` times. Best when `step` is expensive and states are small.
+pub fn hashed<S, F>(initial: S, mut step: F) -> (usize, usize)
+where
+    S: Hash + Eq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let mut seen: HashMap<S, usize> = HashMap::new();
+    let mut state = initial;
+    let mut index = 0;
+
+    loop {
+        if let Some(&first) = seen.get(&state) {
+            return (first, index - first);
+        }
+
+        let next = step(&state);
+        seen.insert(state, index);
+        state = next;
+        index += 1;
+    }
+}
+
+/// The cycle detectors `nth_state` can use. `hashed` is left out as it
+/// needs states to be `Hash`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Detector {
+    Brent,
+    Floyd,
+}
+
+/// The state after `n` steps. The search for a cycle is given up after about
+/// `n` steps, in which case the states are simply stepped through, so this
+/// also works on sequences that never repeat. When a cycle is found it is
+/// used to skip ahead, so huge values of `n` cost little more than
+/// `mu + lambda` steps.
+pub fn nth_state<S, F>(initial: S, mut step: F, n: usize, detector: Detector) -> S
+where
+    S: PartialEq + Clone,
+    F: FnMut(&S) -> S,
+{
+    let cycle = match detector {
+        Detector::Brent => brent_within(initial.clone(), &mut step, n),
+        Detector::Floyd => floyd_within(initial.clone(), &mut step, n),
+    };
+
+    let target = match cycle {
+        Some((mu, lambda)) if n >= mu => mu + (n - mu) % lambda,
+        _ => n,
+    };
+
+    (0..target).fold(initial, |state, _| step(&state))
+}
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    /// 0, 1, 2, 3, then 4..=10 repeating.
+    fn step(value: &u32) -> u32 {
+        if *value == 10 {
+            4
+        } else {
+            value + 1
+        }
+    }
+
+    #[test]
+    fn test_cycle_detection() {
+        assert_eq!(brent(0, step), (4, 7));
+        assert_eq!(floyd(0, step), (4, 7));
+        assert_eq!(hashed(0, step), (4, 7));
+
+        assert_eq!(brent(5, |value: &u32| value * 2 % 7), (0, 3));
+        assert_eq!(hashed(7, |_: &u32| 7), (0, 1));
+    }
+
+    #[test]
+    fn test_bounded_detection() {
+        assert_eq!(brent_within(0, step, 100), Some((4, 7)));
+        assert_eq!(floyd_within(0, step, 100), Some((4, 7)));
+        assert_eq!(brent_within(0, step, 3), None);
+        assert_eq!(floyd_within(0u64, |value| value + 1, 1000), None);
+    }
+
+    #[test]
+    fn test_nth_state() {
+        for detector in [Detector::Brent, Detector::Floyd] {
+            assert_eq!(nth_state(0, step, 0, detector), 0);
+            assert_eq!(nth_state(0, step, 3, detector), 3);
+            assert_eq!(nth_state(0, step, 11, detector), 4);
+            assert_eq!(
+                nth_state(0, step, 1_000_000_000, detector),
+                4 + (1_000_000_000 - 4) % 7
+            );
+        }
+    }
+
+    #[test]
+    fn test_nth_state_without_cycle() {
+        assert_eq!(nth_state(0u64, |value| value + 1, 0, Detector::Brent), 0);
+        assert_eq!(
+            nth_state(0u64, |value| value + 1, 500, Detector::Floyd),
+            500
+        );
+    }
+}
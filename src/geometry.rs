@@ -0,0 +1,200 @@
+use std::collections::{HashMap, HashSet};
+
+use num::Integer;
+
+use crate::maps::{Coordinate, Direction};
+
+/// Twice the area enclosed by a simple polygon, using the shoelace formula.
+/// Doubling keeps the result exact for lattice polygons, whose area can be a
+/// half-integer. The polygon is closed implicitly.
+pub fn double_area(vertices: &[Coordinate<i64>]) -> i64 {
+    let n = vertices.len();
+
+    (0..n)
+        .map(|i| vertices[i].cross(&vertices[(i + 1) % n]))
+        .sum::<i64>()
+        .abs()
+}
+
+/// The area enclosed by a simple polygon.
+pub fn area(vertices: &[Coordinate<i64>]) -> f64 {
+    double_area(vertices) as f64 / 2.0
+}
+
+/// The number of lattice points lying on the edges of a polygon.
+pub fn boundary_points(vertices: &[Coordinate<i64>]) -> i64 {
+    let n = vertices.len();
+
+    (0..n)
+        .map(|i| {
+            let edge = vertices[(i + 1) % n] - vertices[i];
+            edge.x.gcd(&edge.y)
+        })
+        .sum()
+}
+
+/// The number of lattice points strictly inside a lattice polygon, by Pick's
+/// theorem.
+pub fn interior_points(vertices: &[Coordinate<i64>]) -> i64 {
+    (double_area(vertices) - boundary_points(vertices) + 2) / 2
+}
+
+/// The number of lattice points inside or on a lattice polygon, such as the
+/// number of cells dug out when a trench along the polygon is filled in.
+pub fn enclosed_points(vertices: &[Coordinate<i64>]) -> i64 {
+    interior_points(vertices) + boundary_points(vertices)
+}
+
+/// The corners visited by following `steps` from the origin, e.g. dig
+/// instructions like `R 6`. The origin is included once.
+pub fn vertices_from_steps<I>(steps: I) -> Vec<Coordinate<i64>>
+where
+    I: IntoIterator<Item = (Direction, i64)>,
+{
+    let mut current = Coordinate { x: 0, y: 0 };
+    let mut vertices = vec![current];
+
+    for (direction, length) in steps {
+        current += direction.offset() * length;
+        vertices.push(current);
+    }
+
+    if vertices.len() > 1 && vertices.last() == vertices.first() {
+        vertices.pop();
+    }
+
+    vertices
+}
+
+/// The corners of the outer boundary of a set of unit cells, where cell
+/// `(x, y)` covers the square from `(x, y)` to `(x + 1, y + 1)`. Corners are
+/// listed clockwise (with `y` pointing down) from the top-left, and holes are
+/// ignored.
+pub fn trace_boundary(cells: &HashSet<Coordinate<i64>>) -> Vec<Coordinate<i64>> {
+    let Some(&first) = cells.iter().min_by_key(|cell| (cell.y, cell.x)) else {
+        return vec![];
+    };
+
+    let mut edges: HashMap<Coordinate<i64>, Vec<Coordinate<i64>>> = HashMap::new();
+
+    for &Coordinate { x, y } in cells {
+        let corners = [
+            Coordinate { x, y },
+            Coordinate { x: x + 1, y },
+            Coordinate { x: x + 1, y: y + 1 },
+            Coordinate { x, y: y + 1 },
+        ];
+        let outside = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+        for (side, (dx, dy)) in outside.into_iter().enumerate() {
+            if !cells.contains(&Coordinate {
+                x: x + dx,
+                y: y + dy,
+            }) {
+                edges
+                    .entry(corners[side])
+                    .or_default()
+                    .push(corners[(side + 1) % 4] - corners[side]);
+            }
+        }
+    }
+
+    let start = first;
+    let mut heading = Coordinate { x: 1, y: 0 };
+    let mut current = start + heading;
+    let mut vertices = vec![start];
+
+    while current != start {
+        let left = Coordinate {
+            x: heading.y,
+            y: -heading.x,
+        };
+        let right = -left;
+
+        let next = [left, heading, right]
+            .into_iter()
+            .find(|step| {
+                edges
+                    .get(&current)
+                    .is_some_and(|steps| steps.contains(step))
+            })
+            .unwrap();
+
+        if next != heading {
+            vertices.push(current);
+            heading = next;
+        }
+
+        current += heading;
+    }
+
+    vertices
+}
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    #[test]
+    fn test_shoelace() {
+        let square = [
+            Coordinate { x: 0, y: 0 },
+            Coordinate { x: 4, y: 0 },
+            Coordinate { x: 4, y: 3 },
+            Coordinate { x: 0, y: 3 },
+        ];
+
+        assert_eq!(area(&square), 12.0);
+        assert_eq!(boundary_points(&square), 14);
+        assert_eq!(interior_points(&square), 6);
+        assert_eq!(enclosed_points(&square), 20);
+
+        let triangle = [
+            Coordinate { x: 0, y: 0 },
+            Coordinate { x: 3, y: 0 },
+            Coordinate { x: 0, y: 1 },
+        ];
+        assert_eq!(double_area(&triangle), 3);
+    }
+
+    #[test]
+    fn test_vertices_from_steps() {
+        use Direction::*;
+
+        let steps = [
+            (East, 6),
+            (South, 5),
+            (West, 2),
+            (South, 2),
+            (East, 2),
+            (South, 2),
+            (West, 5),
+            (North, 2),
+            (West, 1),
+            (North, 2),
+            (East, 2),
+            (North, 3),
+            (West, 2),
+            (North, 2),
+        ];
+        let vertices = vertices_from_steps(steps);
+
+        assert_eq!(vertices.len(), 14);
+        assert_eq!(enclosed_points(&vertices), 62);
+    }
+
+    #[test]
+    fn test_trace_boundary() {
+        // A sideways T: 8 sides, 5 cells.
+        let cells: HashSet<Coordinate<i64>> = [(0, 0), (0, 1), (1, 1), (2, 1), (0, 2)]
+            .into_iter()
+            .map(|(x, y)| Coordinate { x, y })
+            .collect();
+
+        let outline = trace_boundary(&cells);
+
+        assert_eq!(outline.len(), 8);
+        assert_eq!(outline[0], Coordinate { x: 0, y: 0 });
+        assert_eq!(area(&outline), 5.0);
+    }
+}
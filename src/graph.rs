@@ -0,0 +1,291 @@
+use std::{
+    collections::{HashMap, HashSet},
+    str::FromStr,
+};
+
+use crate::disjoint_set::DisjointSet;
+
+pub type NodeId = usize;
+
+/// An undirected graph of named nodes. Names are interned, so nodes are
+/// referred to by dense `NodeId`s in the order they were first seen.
+#[derive(Clone, Debug, Default)]
+pub struct Graph {
+    names: Vec<String>,
+    ids: HashMap<String, NodeId>,
+    adjacency: Vec<HashSet<NodeId>>,
+}
+
+impl FromStr for Graph {
+    type Err = String;
+
+    /// Parses one `a-b` edge per line. Edges from a node to itself are
+    /// rejected.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut graph = Graph::new();
+
+        for line in s.lines().map(str::trim).filter(|line| !line.is_empty()) {
+            let (a, b) = line
+                .split_once('-')
+                .map(|(a, b)| (a.trim(), b.trim()))
+                .filter(|(a, b)| !a.is_empty() && !b.is_empty())
+                .ok_or_else(|| format!("Invalid edge '{line}'"))?;
+
+            if a == b {
+                return Err(format!("Edge '{line}' connects a node to itself"));
+            }
+
+            let (a, b) = (graph.intern(a), graph.intern(b));
+            graph.add_edge(a, b);
+        }
+
+        Ok(graph)
+    }
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The id of the node called `name`, adding it if it is new.
+    pub fn intern(&mut self, name: &str) -> NodeId {
+        if let Some(&id) = self.ids.get(name) {
+            return id;
+        }
+
+        let id = self.names.len();
+        self.names.push(name.to_string());
+        self.ids.insert(name.to_string(), id);
+        self.adjacency.push(HashSet::new());
+
+        id
+    }
+
+    /// Connects `a` and `b`. Self-loops are ignored, as a node is never its
+    /// own neighbour.
+    pub fn add_edge(&mut self, a: NodeId, b: NodeId) {
+        if a == b {
+            return;
+        }
+
+        self.adjacency[a].insert(b);
+        self.adjacency[b].insert(a);
+    }
+
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    pub fn nodes(&self) -> std::ops::Range<NodeId> {
+        0..self.names.len()
+    }
+
+    pub fn id(&self, name: &str) -> Option<NodeId> {
+        self.ids.get(name).copied()
+    }
+
+    pub fn name(&self, id: NodeId) -> &str {
+        &self.names[id]
+    }
+
+    pub fn neighbours(&self, id: NodeId) -> &HashSet<NodeId> {
+        &self.adjacency[id]
+    }
+
+    pub fn degree(&self, id: NodeId) -> usize {
+        self.adjacency[id].len()
+    }
+
+    pub fn has_edge(&self, a: NodeId, b: NodeId) -> bool {
+        self.adjacency[a].contains(&b)
+    }
+
+    /// Every set of three mutually connected nodes, each listed once in
+    /// ascending id order.
+    pub fn triangles(&self) -> Vec<[NodeId; 3]> {
+        let mut triangles = vec![];
+
+        for a in self.nodes() {
+            for &b in self.adjacency[a].iter().filter(|&&b| b > a) {
+                for &c in self.adjacency[b].iter().filter(|&&c| c > b) {
+                    if self.has_edge(a, c) {
+                        triangles.push([a, b, c]);
+                    }
+                }
+            }
+        }
+
+        triangles
+    }
+
+    /// Every clique that cannot be extended by another node, found with the
+    /// Bron–Kerbosch algorithm using pivoting.
+    pub fn maximal_cliques(&self) -> Vec<Vec<NodeId>> {
+        let mut cliques = vec![];
+
+        self.bron_kerbosch(
+            &mut vec![],
+            self.nodes().collect(),
+            HashSet::new(),
+            &mut cliques,
+        );
+
+        cliques
+    }
+
+    fn bron_kerbosch(
+        &self,
+        clique: &mut Vec<NodeId>,
+        mut candidates: HashSet<NodeId>,
+        mut excluded: HashSet<NodeId>,
+        cliques: &mut Vec<Vec<NodeId>>,
+    ) {
+        if candidates.is_empty() {
+            if excluded.is_empty() {
+                let mut found = clique.clone();
+                found.sort();
+                cliques.push(found);
+            }
+
+            return;
+        }
+
+        let pivot = *candidates
+            .union(&excluded)
+            .max_by_key(|&&node| self.adjacency[node].intersection(&candidates).count())
+            .unwrap();
+
+        let choices: Vec<NodeId> = candidates
+            .difference(&self.adjacency[pivot])
+            .copied()
+            .collect();
+
+        for node in choices {
+            let neighbours = &self.adjacency[node];
+
+            clique.push(node);
+            self.bron_kerbosch(
+                clique,
+                candidates.intersection(neighbours).copied().collect(),
+                excluded.intersection(neighbours).copied().collect(),
+                cliques,
+            );
+            clique.pop();
+
+            candidates.remove(&node);
+            excluded.insert(node);
+        }
+    }
+
+    /// The largest clique in the graph, in ascending id order.
+    pub fn maximum_clique(&self) -> Vec<NodeId> {
+        self.maximal_cliques()
+            .into_iter()
+            .max_by_key(Vec::len)
+            .unwrap_or_default()
+    }
+
+    /// The nodes of each connected component, in ascending id order.
+    pub fn connected_components(&self) -> Vec<Vec<NodeId>> {
+        let mut set = DisjointSet::new(self.len());
+
+        for a in self.nodes() {
+            for &b in &self.adjacency[a] {
+                set.union(a, b);
+            }
+        }
+
+        let mut components: HashMap<usize, Vec<NodeId>> = HashMap::new();
+
+        for node in self.nodes() {
+            components.entry(set.find(node)).or_default().push(node);
+        }
+
+        let mut components: Vec<Vec<NodeId>> = components.into_values().collect();
+        components.sort();
+
+        components
+    }
+}
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    const EDGES: &str = "a-b
+b-c
+c-a
+c-d
+d-e
+e-f
+f-d
+d-g
+e-g
+f-g
+x-y";
+
+    fn names(graph: &Graph, ids: &[NodeId]) -> Vec<String> {
+        let mut names: Vec<String> = ids.iter().map(|&id| graph.name(id).to_string()).collect();
+        names.sort();
+        names
+    }
+
+    #[test]
+    fn test_parse() {
+        let graph: Graph = EDGES.parse().unwrap();
+
+        assert_eq!(graph.len(), 9);
+        assert_eq!(graph.degree(graph.id("d").unwrap()), 4);
+        assert!(graph.has_edge(graph.id("y").unwrap(), graph.id("x").unwrap()));
+        assert!("a-b\nc".parse::<Graph>().is_err());
+        assert!("a-b\n-c".parse::<Graph>().is_err());
+        assert!("aa-aa".parse::<Graph>().is_err());
+
+        let graph: Graph = "a - b\nb -c".parse().unwrap();
+        assert_eq!(graph.len(), 3);
+        assert_eq!(graph.degree(graph.id("b").unwrap()), 2);
+    }
+
+    #[test]
+    fn test_self_loop() {
+        let mut graph = Graph::new();
+        let (a, b) = (graph.intern("a"), graph.intern("b"));
+        graph.add_edge(a, a);
+        graph.add_edge(a, b);
+
+        assert_eq!(graph.degree(a), 1);
+        assert_eq!(graph.maximum_clique(), vec![a, b]);
+    }
+
+    #[test]
+    fn test_triangles() {
+        let graph: Graph = EDGES.parse().unwrap();
+
+        assert_eq!(graph.triangles().len(), 5);
+    }
+
+    #[test]
+    fn test_cliques() {
+        let graph: Graph = EDGES.parse().unwrap();
+
+        assert_eq!(
+            names(&graph, &graph.maximum_clique()),
+            vec!["d", "e", "f", "g"]
+        );
+        assert_eq!(graph.maximal_cliques().len(), 4);
+    }
+
+    #[test]
+    fn test_connected_components() {
+        let graph: Graph = EDGES.parse().unwrap();
+        let components = graph.connected_components();
+
+        assert_eq!(components.len(), 2);
+        assert_eq!(names(&graph, &components[1]), vec!["x", "y"]);
+    }
+}
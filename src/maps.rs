@@ -1,10 +1,31 @@
 use std::{
+    collections::HashMap,
     fmt::{Debug, Display},
-    ops::{Add, AddAssign, Sub, SubAssign},
+    ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign},
     str::FromStr,
 };
 
-use num::{CheckedAdd, CheckedSub, Integer, Unsigned};
+use num::{CheckedAdd, CheckedSub, Integer, Signed, Unsigned};
+
+mod automaton;
+mod connectivity;
+mod distances;
+mod entities;
+mod hex;
+mod point;
+mod regions;
+mod sparse_grid;
+mod voxel_grid;
+mod walker;
+
+pub use automaton::{Automaton, Edges, Neighbourhood};
+pub use entities::{Entity, EntityGrid, EntityId};
+pub use hex::{CubeHex, Hex, HexOrientation};
+pub use point::Point;
+pub use regions::{Connectivity, Region, Regions};
+pub use sparse_grid::SparseGrid;
+pub use voxel_grid::{Voxel, VoxelGrid};
+pub use walker::{Move, WalkOutcome, Walker};
 
 #[derive(PartialEq, Eq, Hash, Copy, Clone)]
 pub struct Coordinate<T> {
@@ -103,6 +124,39 @@ impl<T: SubAssign> SubAssign for Coordinate<T> {
     }
 }
 
+impl<T: Mul<Output = T> + Copy> Mul<T> for Coordinate<T> {
+    type Output = Self;
+
+    fn mul(self, scalar: T) -> Self::Output {
+        Self {
+            x: self.x * scalar,
+            y: self.y * scalar,
+        }
+    }
+}
+
+impl<T: Div<Output = T> + Copy> Div<T> for Coordinate<T> {
+    type Output = Self;
+
+    fn div(self, scalar: T) -> Self::Output {
+        Self {
+            x: self.x / scalar,
+            y: self.y / scalar,
+        }
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Coordinate<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self {
+            x: -self.x,
+            y: -self.y,
+        }
+    }
+}
+
 impl<T: Eq + PartialEq + Ord + Copy> Ord for Coordinate<T> {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
         (self.y, self.x).cmp(&(other.y, other.x))
@@ -231,6 +285,73 @@ impl<T: Integer + Copy> Coordinate<T> {
     }
 }
 
+impl<T: Copy + Ord + Add<Output = T> + Sub<Output = T> + Mul<Output = T>> Coordinate<T> {
+    fn abs_diff(a: T, b: T) -> T {
+        if a > b {
+            a - b
+        } else {
+            b - a
+        }
+    }
+
+    pub fn manhattan(&self, other: &Self) -> T {
+        Self::abs_diff(self.x, other.x) + Self::abs_diff(self.y, other.y)
+    }
+
+    pub fn chebyshev(&self, other: &Self) -> T {
+        Self::abs_diff(self.x, other.x).max(Self::abs_diff(self.y, other.y))
+    }
+
+    /// Squared Euclidean distance, which stays exact for integer coordinates.
+    pub fn distance_squared(&self, other: &Self) -> T {
+        let dx = Self::abs_diff(self.x, other.x);
+        let dy = Self::abs_diff(self.y, other.y);
+
+        dx * dx + dy * dy
+    }
+
+    pub fn dot(&self, other: &Self) -> T {
+        self.x * other.x + self.y * other.y
+    }
+}
+
+impl<T: Integer + Signed + Copy> Coordinate<T> {
+    pub fn signum(&self) -> Self {
+        Coordinate {
+            x: self.x.signum(),
+            y: self.y.signum(),
+        }
+    }
+
+    /// The z component of the cross product of the two vectors.
+    pub fn cross(&self, other: &Self) -> T {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Wraps both components into `0..bounds.x` and `0..bounds.y`.
+    pub fn rem_euclid(&self, bounds: &Self) -> Self {
+        Coordinate {
+            x: self.x.mod_floor(&bounds.x),
+            y: self.y.mod_floor(&bounds.y),
+        }
+    }
+
+    /// The smallest lattice step from `self` towards `other`, i.e. the offset
+    /// divided by the gcd of its components. Stepping by it from `self` visits
+    /// every lattice point on the line to `other`. Returns `None` when the two
+    /// points are the same.
+    pub fn reduced_direction_to(&self, other: &Self) -> Option<Self> {
+        let offset = *other - *self;
+        let divisor = offset.x.gcd(&offset.y);
+
+        if divisor.is_zero() {
+            return None;
+        }
+
+        Some(offset / divisor)
+    }
+}
+
 impl<T: Integer + Copy + CheckedSub + CheckedAdd + Unsigned> Coordinate<T> {
     pub fn checked_neighbour(&self, direction: Direction) -> Option<Self> {
         match direction {
@@ -288,9 +409,119 @@ pub enum Direction {
     West,
 }
 
+impl Direction {
+    /// The step taken when moving one cell in this direction.
+    pub fn offset<T: Integer + Signed + Copy>(&self) -> Coordinate<T> {
+        Coordinate {
+            x: T::zero(),
+            y: T::zero(),
+        }
+        .neighbour(*self)
+    }
+
+    /// This direction rotated a quarter turn clockwise.
+    pub fn turn_right(&self) -> Direction {
+        match self {
+            Direction::North => Direction::East,
+            Direction::NorthEast => Direction::SouthEast,
+            Direction::East => Direction::South,
+            Direction::SouthEast => Direction::SouthWest,
+            Direction::South => Direction::West,
+            Direction::SouthWest => Direction::NorthWest,
+            Direction::West => Direction::North,
+            Direction::NorthWest => Direction::NorthEast,
+        }
+    }
+
+    /// This direction rotated a quarter turn anticlockwise.
+    pub fn turn_left(&self) -> Direction {
+        self.turn_right().opposite()
+    }
+
+    pub fn opposite(&self) -> Direction {
+        self.turn_right().turn_right()
+    }
+}
+
+/// How a grid treats coordinates that step off one of its edges.
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug, Default)]
+pub enum Topology {
+    #[default]
+    Bounded,
+    WrapX,
+    WrapY,
+    Torus,
+}
+
+impl Topology {
+    pub fn wraps_x(&self) -> bool {
+        matches!(self, Topology::WrapX | Topology::Torus)
+    }
+
+    pub fn wraps_y(&self) -> bool {
+        matches!(self, Topology::WrapY | Topology::Torus)
+    }
+
+    /// Maps `coordinate` onto a `width` by `height` grid, wrapping the axes
+    /// that wrap. Returns `None` if it falls off a bounded axis.
+    pub fn resolve<T: Integer + Copy>(
+        &self,
+        coordinate: Coordinate<T>,
+        width: T,
+        height: T,
+    ) -> Option<Coordinate<T>> {
+        let resolve_axis = |value: T, size: T, wraps: bool| {
+            if wraps {
+                Some(value.mod_floor(&size))
+            } else if value >= T::zero() && value < size {
+                Some(value)
+            } else {
+                None
+            }
+        };
+
+        Some(Coordinate {
+            x: resolve_axis(coordinate.x, width, self.wraps_x())?,
+            y: resolve_axis(coordinate.y, height, self.wraps_y())?,
+        })
+    }
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub enum MarkerError {
+    Parse(String),
+    Missing(char),
+    Duplicate {
+        marker: char,
+        first: Coordinate<usize>,
+        second: Coordinate<usize>,
+    },
+}
+
+impl Display for MarkerError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MarkerError::Parse(error) => write!(f, "{}", error),
+            MarkerError::Missing(marker) => write!(f, "Marker '{}' not found in input", marker),
+            MarkerError::Duplicate {
+                marker,
+                first,
+                second,
+            } => write!(
+                f,
+                "Marker '{}' found more than once ({}) and ({})",
+                marker, first, second
+            ),
+        }
+    }
+}
+
+impl std::error::Error for MarkerError {}
+
 pub struct MapData<T> {
     pub width: usize,
     pub height: usize,
+    pub topology: Topology,
     data: Vec<Vec<T>>,
 }
 
@@ -302,10 +533,16 @@ where
         Self {
             width: width.into(),
             height: height.into(),
+            topology: Topology::Bounded,
             data,
         }
     }
 
+    pub fn with_topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
     pub fn new_from_str(input_data: &str) -> Result<Self, String>
     where
         T: From<char>,
@@ -345,10 +582,60 @@ where
         Ok(Self {
             width,
             height,
+            topology: Topology::Bounded,
             data,
         })
     }
 
+    /// Parses a grid in which each of `markers` appears exactly once, such as
+    /// a start `S` and end `E`. Marker cells are stored as `floor` and their
+    /// positions are returned alongside the map.
+    pub fn parse_with_markers(
+        input_data: &str,
+        markers: &[char],
+        floor: T,
+    ) -> Result<(Self, HashMap<char, Coordinate<usize>>), MarkerError>
+    where
+        T: From<char>,
+    {
+        let mut found: HashMap<char, Coordinate<usize>> = HashMap::new();
+
+        for (y, line) in input_data.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                if !markers.contains(&c) {
+                    continue;
+                }
+
+                let coordinate = Coordinate { x, y };
+
+                if let Some(&first) = found.get(&c) {
+                    return Err(MarkerError::Duplicate {
+                        marker: c,
+                        first,
+                        second: coordinate,
+                    });
+                }
+
+                found.insert(c, coordinate);
+            }
+        }
+
+        if let Some(&missing) = markers.iter().find(|marker| !found.contains_key(marker)) {
+            return Err(MarkerError::Missing(missing));
+        }
+
+        let map = Self::new_from_str_with_parser(input_data, |c| {
+            Ok(if markers.contains(&c) {
+                floor
+            } else {
+                T::from(c)
+            })
+        })
+        .map_err(MarkerError::Parse)?;
+
+        Ok((map, found))
+    }
+
     pub fn get<C: PartialEq + PartialOrd + Into<usize> + From<usize> + Copy>(
         &self,
         coordinate: &Coordinate<C>,
@@ -362,6 +649,19 @@ where
         None
     }
 
+    /// Replaces the cell at `coordinate`, returning the old value, or `None`
+    /// if `coordinate` is outside the map.
+    pub fn set<C: PartialEq + PartialOrd + Into<usize> + From<usize> + Copy>(
+        &mut self,
+        coordinate: &Coordinate<C>,
+        value: T,
+    ) -> Option<T> {
+        let previous = self.get(coordinate)?;
+        self.data[coordinate.y.into()][coordinate.x.into()] = value;
+
+        Some(previous)
+    }
+
     pub unsafe fn unchecked_get<C: PartialEq + PartialOrd + Into<usize> + From<usize> + Copy>(
         &self,
         coordinate: &Coordinate<C>,
@@ -369,24 +669,77 @@ where
         self.data[coordinate.y.into()][coordinate.x.into()]
     }
 
+    /// The cell one step from `coordinate` in `direction`, taking the map's
+    /// topology into account.
+    pub fn neighbour<C: PartialEq + PartialOrd + Into<usize> + From<usize> + Copy>(
+        &self,
+        coordinate: &Coordinate<C>,
+        direction: Direction,
+    ) -> Option<Coordinate<C>> {
+        let current = Coordinate {
+            x: coordinate.x.into() as i64,
+            y: coordinate.y.into() as i64,
+        };
+
+        self.topology
+            .resolve(
+                current + direction.offset(),
+                self.width as i64,
+                self.height as i64,
+            )
+            .map(|resolved| Coordinate {
+                x: (resolved.x as usize).into(),
+                y: (resolved.y as usize).into(),
+            })
+    }
+
     pub fn get_valid_adjacent_coordinates<
-        C: Integer + PartialEq + PartialOrd + Into<usize> + From<usize> + Copy,
+        C: PartialEq + PartialOrd + Into<usize> + From<usize> + Copy,
     >(
         &self,
         coordinate: &Coordinate<C>,
     ) -> Vec<Coordinate<C>> {
-        let adjacent: Vec<Coordinate<C>> = coordinate
-            .neighbours()
-            .into_iter()
-            .filter(|coordinate| {
-                (0..self.width).contains(&coordinate.x.into())
-                    && (0..self.height).contains(&coordinate.y.into())
-            })
-            .collect();
+        let mut adjacent: Vec<Coordinate<C>> = Vec::with_capacity(4);
+
+        for direction in [
+            Direction::West,
+            Direction::East,
+            Direction::North,
+            Direction::South,
+        ] {
+            if let Some(neighbour) = self.neighbour(coordinate, direction) {
+                if !adjacent.contains(&neighbour) {
+                    adjacent.push(neighbour);
+                }
+            }
+        }
 
         adjacent
     }
 
+    /// Every cell visited by stepping from `start` in `direction`, not
+    /// including `start`. The ray stops at a bounded edge, or once it wraps
+    /// back around to `start`.
+    pub fn ray<C: PartialEq + PartialOrd + Into<usize> + From<usize> + Copy>(
+        &self,
+        start: &Coordinate<C>,
+        direction: Direction,
+    ) -> Vec<Coordinate<C>> {
+        let mut ray: Vec<Coordinate<C>> = Vec::new();
+        let mut current = *start;
+
+        while let Some(next) = self.neighbour(&current, direction) {
+            if next == *start {
+                break;
+            }
+
+            ray.push(next);
+            current = next;
+        }
+
+        ray
+    }
+
     pub fn enumerate<C: PartialEq + PartialOrd + Into<usize> + From<usize> + Copy>(
         &self,
     ) -> impl Iterator<Item = (Coordinate<C>, &T)> {
@@ -403,3 +756,113 @@ where
         })
     }
 }
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    #[test]
+    fn test_coordinate_arithmetic() {
+        let a = Coordinate { x: 3i32, y: -4 };
+
+        assert_eq!(a * 2, Coordinate { x: 6, y: -8 });
+        assert_eq!(a / 3, Coordinate { x: 1, y: -1 });
+        assert_eq!(-a, Coordinate { x: -3, y: 4 });
+        assert_eq!(a.signum(), Coordinate { x: 1, y: -1 });
+        assert_eq!(
+            a.rem_euclid(&Coordinate { x: 2, y: 3 }),
+            Coordinate { x: 1, y: 2 }
+        );
+    }
+
+    #[test]
+    fn test_coordinate_distances() {
+        let a = Coordinate { x: 1usize, y: 5 };
+        let b = Coordinate { x: 4usize, y: 1 };
+
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.chebyshev(&b), 4);
+        assert_eq!(a.distance_squared(&b), 25);
+        assert_eq!(a.dot(&b), 9);
+        assert_eq!(
+            Coordinate { x: 1i32, y: 0 }.cross(&Coordinate { x: 0, y: 1 }),
+            1
+        );
+    }
+
+    #[test]
+    fn test_topology() {
+        let map: MapData<char> = MapData::new_from_str("abc\ndef").unwrap();
+        let corner = Coordinate { x: 0usize, y: 0 };
+
+        assert_eq!(map.get_valid_adjacent_coordinates(&corner).len(), 2);
+        assert_eq!(map.neighbour(&corner, Direction::West), None);
+
+        let map = map.with_topology(Topology::WrapX);
+
+        assert_eq!(
+            map.neighbour(&corner, Direction::West),
+            Some(Coordinate { x: 2, y: 0 })
+        );
+        assert_eq!(map.neighbour(&corner, Direction::North), None);
+        assert_eq!(map.get_valid_adjacent_coordinates(&corner).len(), 3);
+
+        let map = map.with_topology(Topology::Torus);
+
+        assert_eq!(
+            map.neighbour(&corner, Direction::NorthWest),
+            Some(Coordinate { x: 2, y: 1 })
+        );
+        assert_eq!(map.ray(&corner, Direction::East).len(), 2);
+        assert_eq!(
+            Topology::Torus.resolve(Coordinate { x: -1, y: 7 }, 11, 7),
+            Some(Coordinate { x: 10, y: 0 })
+        );
+    }
+
+    #[test]
+    fn test_parse_with_markers() {
+        let (map, markers) =
+            MapData::<char>::parse_with_markers("#S.\n..E", &['S', 'E'], '.').unwrap();
+
+        assert_eq!(markers[&'S'], Coordinate { x: 1, y: 0 });
+        assert_eq!(markers[&'E'], Coordinate { x: 2, y: 1 });
+        assert_eq!(map.get(&Coordinate { x: 1usize, y: 0 }), Some('.'));
+
+        assert_eq!(
+            MapData::<char>::parse_with_markers("#S.\n...", &['S', 'E'], '.').err(),
+            Some(MarkerError::Missing('E'))
+        );
+        assert_eq!(
+            MapData::<char>::parse_with_markers("#S.\n.S.", &['S'], '.').err(),
+            Some(MarkerError::Duplicate {
+                marker: 'S',
+                first: Coordinate { x: 1, y: 0 },
+                second: Coordinate { x: 1, y: 1 },
+            })
+        );
+    }
+
+    #[test]
+    fn test_direction_turns() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::SouthEast.opposite(), Direction::NorthWest);
+        assert_eq!(Direction::NorthEast.turn_left(), Direction::NorthWest);
+    }
+
+    #[test]
+    fn test_reduced_direction() {
+        let a = Coordinate { x: 1i32, y: 1 };
+
+        assert_eq!(
+            a.reduced_direction_to(&Coordinate { x: 7, y: -8 }),
+            Some(Coordinate { x: 2, y: -3 })
+        );
+        assert_eq!(
+            a.reduced_direction_to(&Coordinate { x: 1, y: 5 }),
+            Some(Coordinate { x: 0, y: 1 })
+        );
+        assert_eq!(a.reduced_direction_to(&a), None);
+    }
+}
@@ -0,0 +1,435 @@
+use std::thread;
+
+use super::{Coordinate, MapData, Topology};
+use crate::simulation::Simulation;
+
+/// The cells a cellular automaton rule can see around each cell.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum Neighbourhood {
+    /// The four orthogonal neighbours.
+    VonNeumann,
+    /// All eight surrounding cells.
+    Moore,
+    /// Any offsets from the cell.
+    Custom(Vec<Coordinate<i64>>),
+}
+
+impl Neighbourhood {
+    /// The offsets of the neighbourhood, in the order the rule sees them.
+    pub fn offsets(&self) -> Vec<Coordinate<i64>> {
+        match self {
+            Neighbourhood::VonNeumann => vec![
+                Coordinate { x: 0, y: -1 },
+                Coordinate { x: 1, y: 0 },
+                Coordinate { x: 0, y: 1 },
+                Coordinate { x: -1, y: 0 },
+            ],
+            Neighbourhood::Moore => (-1..=1)
+                .flat_map(|y| (-1..=1).map(move |x| Coordinate { x, y }))
+                .filter(|offset| *offset != Coordinate { x: 0, y: 0 })
+                .collect(),
+            Neighbourhood::Custom(offsets) => offsets.clone(),
+        }
+    }
+}
+
+/// What an automaton sees beyond the edges of its map.
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+pub enum Edges<T> {
+    /// Nothing: neighbours off the map are `None`.
+    Bounded,
+    /// The opposite edge, as on a torus.
+    Wrapping,
+    /// An endless plane of `background`. The map grows to fit every cell
+    /// that is not `background`, and `origin` tracks where it has moved to.
+    /// The background itself evolves too, as a cell whose neighbours are all
+    /// background.
+    Infinite { background: T },
+}
+
+/// A double-buffered cellular automaton over a `MapData`, created by
+/// `MapData::automaton`.
+pub struct Automaton<T, F> {
+    map: MapData<T>,
+    back: Vec<Vec<T>>,
+    offsets: Vec<Coordinate<i64>>,
+    edges: Edges<T>,
+    rule: F,
+    origin: Coordinate<i64>,
+    generation: usize,
+}
+
+impl<T> MapData<T>
+where
+    T: Copy,
+{
+    /// Turns this map into a cellular automaton. Each step, every cell is
+    /// replaced by `rule(cell, neighbours)`, where `neighbours` follows the
+    /// order of `neighbourhood.offsets()`. Edges are bounded unless changed
+    /// with `Automaton::with_edges`.
+    pub fn automaton<F>(self, neighbourhood: Neighbourhood, rule: F) -> Automaton<T, F>
+    where
+        F: Fn(T, &[Option<T>]) -> T,
+    {
+        Automaton {
+            back: self.data.clone(),
+            map: self.with_topology(Topology::Bounded),
+            offsets: neighbourhood.offsets(),
+            edges: Edges::Bounded,
+            rule,
+            origin: Coordinate { x: 0, y: 0 },
+            generation: 0,
+        }
+    }
+}
+
+fn compute_rows<T, F>(
+    map: &MapData<T>,
+    offsets: &[Coordinate<i64>],
+    edges: Edges<T>,
+    rule: &F,
+    first_row: usize,
+    rows: &mut [Vec<T>],
+) where
+    T: Copy,
+    F: Fn(T, &[Option<T>]) -> T,
+{
+    let mut neighbours: Vec<Option<T>> = Vec::with_capacity(offsets.len());
+
+    for (index, row) in rows.iter_mut().enumerate() {
+        let y = first_row + index;
+
+        for (x, cell) in row.iter_mut().enumerate() {
+            let current = Coordinate {
+                x: x as i64,
+                y: y as i64,
+            };
+
+            neighbours.clear();
+            neighbours.extend(offsets.iter().map(|&offset| {
+                match map
+                    .topology
+                    .resolve(current + offset, map.width as i64, map.height as i64)
+                {
+                    Some(resolved) => Some(map.data[resolved.y as usize][resolved.x as usize]),
+                    None => match edges {
+                        Edges::Infinite { background } => Some(background),
+                        _ => None,
+                    },
+                }
+            }));
+
+            *cell = rule(map.data[y][x], &neighbours);
+        }
+    }
+}
+
+impl<T, F> Automaton<T, F>
+where
+    T: Copy + PartialEq,
+    F: Fn(T, &[Option<T>]) -> T,
+{
+    pub fn with_edges(mut self, edges: Edges<T>) -> Self {
+        self.edges = edges;
+        self.map.topology = match edges {
+            Edges::Wrapping => Topology::Torus,
+            _ => Topology::Bounded,
+        };
+        self
+    }
+
+    pub fn map(&self) -> &MapData<T> {
+        &self.map
+    }
+
+    pub fn into_map(self) -> MapData<T> {
+        self.map
+    }
+
+    /// Where the map's `(0, 0)` cell sits on the infinite plane. Always the
+    /// origin unless the edges are `Infinite`.
+    pub fn origin(&self) -> Coordinate<i64> {
+        self.origin
+    }
+
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+
+    /// The current state of every cell off the map, if the edges are
+    /// `Infinite`.
+    pub fn background(&self) -> Option<T> {
+        match self.edges {
+            Edges::Infinite { background } => Some(background),
+            _ => None,
+        }
+    }
+
+    /// Adds enough `background` around the map for every cell the rule
+    /// could bring to life.
+    fn grow(&mut self, background: T) {
+        let margin = self
+            .offsets
+            .iter()
+            .map(|offset| offset.x.abs().max(offset.y.abs()))
+            .max()
+            .unwrap_or(0) as usize;
+
+        let width = self.map.width + 2 * margin;
+        let mut data = vec![vec![background; width]; margin];
+
+        for row in self.map.data.drain(..) {
+            let mut padded = vec![background; margin];
+            padded.extend(row);
+            padded.extend(vec![background; margin]);
+            data.push(padded);
+        }
+
+        data.extend(vec![vec![background; width]; margin]);
+
+        self.map.width = width;
+        self.map.height = data.len();
+        self.map.data = data;
+        self.origin -= Coordinate {
+            x: margin as i64,
+            y: margin as i64,
+        };
+    }
+
+    /// Cuts the map down to the cells that are not `background`.
+    fn shrink(&mut self, background: T) {
+        let occupied = |x: usize, y: usize| self.map.data[y][x] != background;
+
+        let rows: Vec<usize> = (0..self.map.height)
+            .filter(|&y| (0..self.map.width).any(|x| occupied(x, y)))
+            .collect();
+        let columns: Vec<usize> = (0..self.map.width)
+            .filter(|&x| (0..self.map.height).any(|y| occupied(x, y)))
+            .collect();
+
+        let (Some(&top), Some(&bottom), Some(&left), Some(&right)) =
+            (rows.first(), rows.last(), columns.first(), columns.last())
+        else {
+            self.map = MapData::new(0usize, 0usize, vec![]);
+            return;
+        };
+
+        self.map.data = self.map.data[top..=bottom]
+            .iter()
+            .map(|row| row[left..=right].to_vec())
+            .collect();
+        self.map.width = right - left + 1;
+        self.map.height = bottom - top + 1;
+        self.origin += Coordinate {
+            x: left as i64,
+            y: top as i64,
+        };
+    }
+
+    fn prepare(&mut self) {
+        if let Edges::Infinite { background } = self.edges {
+            self.grow(background);
+        }
+
+        if self.back.len() != self.map.height
+            || self.back.first().map_or(0, Vec::len) != self.map.width
+        {
+            self.back = self.map.data.clone();
+        }
+    }
+
+    fn finish(&mut self) {
+        std::mem::swap(&mut self.map.data, &mut self.back);
+
+        if let Edges::Infinite { background } = self.edges {
+            let background = (self.rule)(background, &vec![Some(background); self.offsets.len()]);
+
+            self.edges = Edges::Infinite { background };
+            self.shrink(background);
+        }
+
+        self.generation += 1;
+    }
+
+    /// Advances every cell by one generation.
+    pub fn step(&mut self) {
+        self.prepare();
+        compute_rows(
+            &self.map,
+            &self.offsets,
+            self.edges,
+            &self.rule,
+            0,
+            &mut self.back,
+        );
+        self.finish();
+    }
+
+    /// Like `step`, but shares the rows out between `threads` threads.
+    pub fn step_parallel(&mut self, threads: usize)
+    where
+        T: Send + Sync,
+        F: Sync,
+    {
+        self.prepare();
+
+        let rows_per_thread = self.map.height.div_ceil(threads.max(1)).max(1);
+        let (map, offsets, edges, rule) = (&self.map, &self.offsets, self.edges, &self.rule);
+
+        thread::scope(|scope| {
+            for (chunk, rows) in self.back.chunks_mut(rows_per_thread).enumerate() {
+                scope.spawn(move || {
+                    compute_rows(map, offsets, edges, rule, chunk * rows_per_thread, rows)
+                });
+            }
+        });
+
+        self.finish();
+    }
+
+    pub fn run(&mut self, generations: usize) {
+        for _ in 0..generations {
+            self.step();
+        }
+    }
+}
+
+impl<T, F> Simulation for Automaton<T, F>
+where
+    T: Copy + PartialEq,
+    F: Fn(T, &[Option<T>]) -> T,
+{
+    type Frame = MapData<T>;
+
+    fn step(&mut self) {
+        Automaton::step(self);
+    }
+
+    fn frame(&self) -> Self::Frame {
+        MapData::new(self.map.width, self.map.height, self.map.data.clone())
+    }
+}
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    fn life(cell: char, neighbours: &[Option<char>]) -> char {
+        let alive = neighbours.iter().filter(|&&n| n == Some('#')).count();
+
+        match (cell, alive) {
+            ('#', 2) | (_, 3) => '#',
+            _ => '.',
+        }
+    }
+
+    fn render(map: &MapData<char>) -> String {
+        (0..map.height)
+            .map(|y| {
+                (0..map.width)
+                    .map(|x| map.get(&Coordinate { x, y }).unwrap())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    const GLIDER: &str = ".#...\n..#..\n###..\n.....\n.....";
+
+    #[test]
+    fn test_bounded() {
+        let map: MapData<char> =
+            MapData::new_from_str(".....\n..#..\n..#..\n..#..\n.....").unwrap();
+        let mut automaton = map.automaton(Neighbourhood::Moore, life);
+
+        automaton.step();
+        assert_eq!(render(automaton.map()), ".....\n.....\n.###.\n.....\n.....");
+
+        automaton.step();
+        assert_eq!(render(automaton.map()), ".....\n..#..\n..#..\n..#..\n.....");
+        assert_eq!(automaton.generation(), 2);
+    }
+
+    #[test]
+    fn test_wrapping() {
+        let map: MapData<char> = MapData::new_from_str(GLIDER).unwrap();
+        let mut automaton = map
+            .automaton(Neighbourhood::Moore, life)
+            .with_edges(Edges::Wrapping);
+
+        automaton.run(20);
+        assert_eq!(render(automaton.map()), GLIDER);
+    }
+
+    #[test]
+    fn test_infinite() {
+        let map: MapData<char> = MapData::new_from_str(GLIDER).unwrap();
+        let mut automaton = map
+            .automaton(Neighbourhood::Moore, life)
+            .with_edges(Edges::Infinite { background: '.' });
+
+        automaton.run(8);
+        assert_eq!(render(automaton.map()), ".#.\n..#\n###");
+        assert_eq!(automaton.origin(), Coordinate { x: 2, y: 2 });
+    }
+
+    #[test]
+    fn test_infinite_background() {
+        let map: MapData<char> = MapData::new_from_str("#").unwrap();
+        let mut automaton = map
+            .automaton(Neighbourhood::Moore, |cell, neighbours| {
+                let lit = neighbours.iter().filter(|&&n| n == Some('#')).count();
+
+                match (cell, lit) {
+                    ('.', 0) => '#',
+                    ('#', 8) => '.',
+                    _ => cell,
+                }
+            })
+            .with_edges(Edges::Infinite { background: '.' });
+
+        automaton.step();
+        assert_eq!(automaton.background(), Some('#'));
+        assert_eq!(render(automaton.map()), "...\n.#.\n...");
+
+        automaton.step();
+        assert_eq!(automaton.background(), Some('.'));
+        assert_eq!(render(automaton.map()), "#####\n#...#\n#.#.#\n#...#\n#####");
+        assert_eq!(automaton.origin(), Coordinate { x: -2, y: -2 });
+    }
+
+    #[test]
+    fn test_parallel() {
+        let map: MapData<char> = MapData::new_from_str(GLIDER).unwrap();
+        let mut sequential = map.automaton(Neighbourhood::Moore, life);
+
+        let map: MapData<char> = MapData::new_from_str(GLIDER).unwrap();
+        let mut parallel = map.automaton(Neighbourhood::Moore, life);
+
+        for _ in 0..3 {
+            sequential.step();
+            parallel.step_parallel(2);
+        }
+
+        assert_eq!(render(sequential.map()), render(parallel.map()));
+    }
+
+    #[test]
+    fn test_custom_neighbourhood() {
+        let map: MapData<u8> = MapData::new(3usize, 1usize, vec![vec![1, 2, 3]]);
+        let right = Neighbourhood::Custom(vec![Coordinate { x: 1, y: 0 }]);
+        let mut automaton = map
+            .automaton(right, |_, neighbours| neighbours[0].unwrap())
+            .with_edges(Edges::Wrapping);
+
+        automaton.step();
+        assert_eq!(
+            automaton.map().get(&Coordinate { x: 0usize, y: 0 }),
+            Some(2)
+        );
+        assert_eq!(
+            automaton.map().get(&Coordinate { x: 2usize, y: 0 }),
+            Some(1)
+        );
+    }
+}
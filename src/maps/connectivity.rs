@@ -0,0 +1,118 @@
+use std::{collections::HashMap, hash::Hash};
+
+use num::{Integer, NumCast};
+
+use super::{Coordinate, MapData, SparseGrid};
+use crate::disjoint_set::DisjointSet;
+
+impl<T> MapData<T>
+where
+    T: Copy,
+{
+    /// The element that represents `coordinate` in sets built by
+    /// `disjoint_set`.
+    pub fn cell_index(&self, coordinate: &Coordinate<usize>) -> usize {
+        coordinate.y * self.width + coordinate.x
+    }
+
+    /// Unions `coordinate` with each orthogonal neighbour for which `open`
+    /// holds, for when cells open up one at a time. Does nothing if
+    /// `coordinate` itself is not open.
+    pub fn union_neighbours<F>(
+        &self,
+        set: &mut DisjointSet,
+        coordinate: &Coordinate<usize>,
+        open: F,
+    ) where
+        F: Fn(T) -> bool,
+    {
+        if !self.get(coordinate).is_some_and(&open) {
+            return;
+        }
+
+        for neighbour in self.get_valid_adjacent_coordinates(coordinate) {
+            if open(self.get(&neighbour).unwrap()) {
+                set.union(self.cell_index(coordinate), self.cell_index(&neighbour));
+            }
+        }
+    }
+
+    /// A set with one element per cell, where orthogonally adjacent cells
+    /// that are both `open` share a set. Closed cells stay on their own.
+    pub fn disjoint_set<F>(&self, open: F) -> DisjointSet
+    where
+        F: Fn(T) -> bool,
+    {
+        let mut set = DisjointSet::new(self.width * self.height);
+
+        for (coordinate, _) in self.enumerate::<usize>() {
+            self.union_neighbours(&mut set, &coordinate, &open);
+        }
+
+        set
+    }
+}
+
+impl<T, C> SparseGrid<T, C>
+where
+    T: Copy,
+    C: Integer + Hash + Copy + NumCast,
+{
+    /// A set with one element per stored cell, where orthogonally adjacent
+    /// stored cells share a set, along with the element for each cell.
+    pub fn disjoint_set(&self) -> (DisjointSet, HashMap<Coordinate<C>, usize>) {
+        let elements: HashMap<Coordinate<C>, usize> = self
+            .iter()
+            .enumerate()
+            .map(|(element, (coordinate, _))| (*coordinate, element))
+            .collect();
+
+        let mut set = DisjointSet::new(elements.len());
+
+        for (coordinate, &element) in &elements {
+            for neighbour in self.occupied_neighbours(coordinate) {
+                set.union(element, elements[&neighbour]);
+            }
+        }
+
+        (set, elements)
+    }
+}
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    #[test]
+    fn test_map_data_disjoint_set() {
+        let mut map: MapData<char> = MapData::new_from_str("..#..\n..#..\n#####").unwrap();
+        let mut set = map.disjoint_set(|cell| cell == '.');
+
+        let left = map.cell_index(&Coordinate { x: 0, y: 0 });
+        let right = map.cell_index(&Coordinate { x: 4, y: 1 });
+
+        assert!(!set.connected(left, right));
+        assert_eq!(set.size_of(left), 4);
+
+        let gap = Coordinate { x: 2, y: 1 };
+        map.set(&gap, '.');
+        map.union_neighbours(&mut set, &gap, |cell| cell == '.');
+
+        assert!(set.connected(left, right));
+        assert_eq!(set.size_of(right), 9);
+    }
+
+    #[test]
+    fn test_sparse_grid_disjoint_set() {
+        let mut grid: SparseGrid<char> = SparseGrid::new('.');
+
+        for (x, y) in [(0, 0), (1, 0), (1, 1), (5, 5), (5, 6), (-3, 0)] {
+            grid.insert(Coordinate { x, y }, '#');
+        }
+
+        let (mut set, elements) = grid.disjoint_set();
+
+        assert_eq!(set.components(), 3);
+        assert_eq!(set.size_of(elements[&Coordinate { x: 1, y: 1 }]), 3);
+    }
+}
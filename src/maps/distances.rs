@@ -0,0 +1,225 @@
+use std::collections::VecDeque;
+
+use super::{Coordinate, MapData};
+
+impl<T> MapData<T>
+where
+    T: Copy,
+{
+    /// The number of steps from the nearest of `starts` to every cell, moving
+    /// orthogonally through cells for which `passable` holds. Unreachable
+    /// cells are `None`.
+    pub fn multi_source_bfs<C, F>(
+        &self,
+        starts: &[Coordinate<C>],
+        passable: F,
+    ) -> MapData<Option<u32>>
+    where
+        C: PartialEq + PartialOrd + Into<usize> + From<usize> + Copy,
+        F: Fn(T) -> bool,
+    {
+        let mut distances: MapData<Option<u32>> = MapData::new(
+            self.width,
+            self.height,
+            vec![vec![None; self.width]; self.height],
+        )
+        .with_topology(self.topology);
+        let mut frontier: VecDeque<Coordinate<usize>> = VecDeque::new();
+
+        for start in starts {
+            let start = Coordinate {
+                x: start.x.into(),
+                y: start.y.into(),
+            };
+
+            if distances.get(&start) == Some(None) {
+                distances.set(&start, Some(0));
+                frontier.push_back(start);
+            }
+        }
+
+        while let Some(current) = frontier.pop_front() {
+            let distance = distances.get(&current).flatten().unwrap();
+
+            for neighbour in self.get_valid_adjacent_coordinates(&current) {
+                if distances.get(&neighbour) == Some(None)
+                    && passable(self.get(&neighbour).unwrap())
+                {
+                    distances.set(&neighbour, Some(distance + 1));
+                    frontier.push_back(neighbour);
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// The number of steps from `start` to every cell. See `multi_source_bfs`.
+    pub fn bfs_distances<C, F>(&self, start: &Coordinate<C>, passable: F) -> MapData<Option<u32>>
+    where
+        C: PartialEq + PartialOrd + Into<usize> + From<usize> + Copy,
+        F: Fn(T) -> bool,
+    {
+        self.multi_source_bfs(&[*start], passable)
+    }
+
+    /// One shortest orthogonal path from `start` to `goal`, including both
+    /// ends, or `None` if `goal` cannot be reached.
+    pub fn shortest_path<C, F>(
+        &self,
+        start: &Coordinate<C>,
+        goal: &Coordinate<C>,
+        passable: F,
+    ) -> Option<Vec<Coordinate<C>>>
+    where
+        C: PartialEq + PartialOrd + Into<usize> + From<usize> + Copy,
+        F: Fn(T) -> bool,
+    {
+        let distances = self.bfs_distances(start, passable);
+
+        let mut current = *goal;
+        let mut remaining = distances.get(&current).flatten()?;
+        let mut path = vec![current];
+
+        while remaining > 0 {
+            remaining -= 1;
+            current = self
+                .get_valid_adjacent_coordinates(&current)
+                .into_iter()
+                .find(|neighbour| distances.get(neighbour) == Some(Some(remaining)))?;
+
+            path.push(current);
+        }
+
+        path.reverse();
+        Some(path)
+    }
+}
+
+impl MapData<Option<u32>> {
+    /// Every pair of reachable cells `(from, to)` at most `max_jump` apart by
+    /// Manhattan distance where jumping straight from `from` to `to` saves at
+    /// least `min_saving` steps over the distances in this map. Each pair is
+    /// returned with the number of steps saved. Only the cells within
+    /// `max_jump` of each cell are checked.
+    pub fn shortcuts(
+        &self,
+        max_jump: usize,
+        min_saving: u32,
+    ) -> Vec<(Coordinate<usize>, Coordinate<usize>, u32)> {
+        let max_jump = max_jump as isize;
+        let mut shortcuts = vec![];
+
+        for (from, from_distance) in self.enumerate::<usize>() {
+            let Some(from_distance) = *from_distance else {
+                continue;
+            };
+
+            for dy in -max_jump..=max_jump {
+                let reach = max_jump - dy.abs();
+
+                for dx in -reach..=reach {
+                    let (x, y) = (from.x as isize + dx, from.y as isize + dy);
+
+                    if x < 0 || y < 0 || x >= self.width as isize || y >= self.height as isize {
+                        continue;
+                    }
+
+                    let to = Coordinate {
+                        x: x as usize,
+                        y: y as usize,
+                    };
+
+                    let Some(Some(to_distance)) = self.get(&to) else {
+                        continue;
+                    };
+
+                    if to_distance <= from_distance {
+                        continue;
+                    }
+
+                    let jump = (dx.abs() + dy.abs()) as u32;
+                    let saving = (to_distance - from_distance).saturating_sub(jump);
+
+                    if saving >= min_saving && saving > 0 {
+                        shortcuts.push((from, to, saving));
+                    }
+                }
+            }
+        }
+
+        shortcuts
+    }
+}
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    const RACETRACK: &str = "###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############";
+
+    #[test]
+    fn test_bfs_distances() {
+        let (map, markers) =
+            MapData::<char>::parse_with_markers(RACETRACK, &['S', 'E'], '.').unwrap();
+        let distances = map.bfs_distances(&markers[&'S'], |cell| cell != '#');
+
+        assert_eq!(distances.get(&markers[&'E']), Some(Some(84)));
+        assert_eq!(distances.get(&Coordinate { x: 0usize, y: 0 }), Some(None));
+
+        let path = map
+            .shortest_path(&markers[&'S'], &markers[&'E'], |cell| cell != '#')
+            .unwrap();
+
+        assert_eq!(path.len(), 85);
+        assert_eq!(path.first(), Some(&markers[&'S']));
+        assert!(path.windows(2).all(|pair| pair[0].manhattan(&pair[1]) == 1));
+    }
+
+    #[test]
+    fn test_multi_source_bfs() {
+        let map: MapData<char> = MapData::new_from_str(".....\n.###.\n.....").unwrap();
+        let distances = map.multi_source_bfs(
+            &[
+                Coordinate { x: 0usize, y: 0 },
+                Coordinate { x: 4usize, y: 2 },
+            ],
+            |cell| cell == '.',
+        );
+
+        assert_eq!(
+            distances.get(&Coordinate { x: 2usize, y: 0 }),
+            Some(Some(2))
+        );
+        assert_eq!(
+            distances.get(&Coordinate { x: 3usize, y: 2 }),
+            Some(Some(1))
+        );
+        assert_eq!(distances.get(&Coordinate { x: 2usize, y: 1 }), Some(None));
+    }
+
+    #[test]
+    fn test_shortcuts() {
+        let (map, markers) =
+            MapData::<char>::parse_with_markers(RACETRACK, &['S', 'E'], '.').unwrap();
+        let distances = map.bfs_distances(&markers[&'S'], |cell| cell != '#');
+
+        assert_eq!(distances.shortcuts(2, 64).len(), 1);
+        assert_eq!(distances.shortcuts(2, 20).len(), 5);
+        assert_eq!(distances.shortcuts(20, 76).len(), 3);
+    }
+}
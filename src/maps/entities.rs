@@ -0,0 +1,238 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use super::{Coordinate, Direction};
+
+pub type EntityId = usize;
+
+/// Something occupying one or more cells of an `EntityGrid`, such as a wall,
+/// a robot or a two-cell-wide box.
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub struct Entity<K> {
+    pub kind: K,
+    pub position: Coordinate<i32>,
+    /// The cells covered by the entity, relative to `position`.
+    pub shape: Vec<Coordinate<i32>>,
+    pub movable: bool,
+}
+
+impl<K> Entity<K> {
+    pub fn cells(&self) -> impl Iterator<Item = Coordinate<i32>> + '_ {
+        self.shape.iter().map(|&offset| self.position + offset)
+    }
+}
+
+/// A set of entities that never overlap, which can push each other around.
+/// Pushes happen in two phases: `push_set` works out which entities would
+/// move without changing anything, then `commit` moves them all at once, so
+/// a blocked push leaves the grid exactly as it was.
+#[derive(Clone)]
+pub struct EntityGrid<K> {
+    entities: Vec<Entity<K>>,
+    occupancy: HashMap<Coordinate<i32>, EntityId>,
+}
+
+impl<K> Default for EntityGrid<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K> EntityGrid<K> {
+    pub fn new() -> Self {
+        Self {
+            entities: vec![],
+            occupancy: HashMap::new(),
+        }
+    }
+
+    /// Places a new entity, failing if any of its cells are already taken.
+    pub fn add(
+        &mut self,
+        kind: K,
+        position: Coordinate<i32>,
+        shape: Vec<Coordinate<i32>>,
+        movable: bool,
+    ) -> Result<EntityId, String> {
+        let id = self.entities.len();
+        let entity = Entity {
+            kind,
+            position,
+            shape,
+            movable,
+        };
+
+        if let Some(cell) = entity
+            .cells()
+            .find(|cell| self.occupancy.contains_key(cell))
+        {
+            return Err(format!("Cell {cell} is already occupied"));
+        }
+
+        for cell in entity.cells() {
+            self.occupancy.insert(cell, id);
+        }
+
+        self.entities.push(entity);
+        Ok(id)
+    }
+
+    pub fn entity(&self, id: EntityId) -> &Entity<K> {
+        &self.entities[id]
+    }
+
+    pub fn entity_at(&self, coordinate: &Coordinate<i32>) -> Option<EntityId> {
+        self.occupancy.get(coordinate).copied()
+    }
+
+    pub fn entities(&self) -> impl Iterator<Item = (EntityId, &Entity<K>)> {
+        self.entities.iter().enumerate()
+    }
+
+    /// Every entity that would have to move for `id` to move one step in
+    /// `direction`, including `id` itself, or `None` if an immovable entity
+    /// is in the way.
+    pub fn push_set(&self, id: EntityId, direction: Direction) -> Option<Vec<EntityId>> {
+        if !self.entities[id].movable {
+            return None;
+        }
+
+        let offset: Coordinate<i32> = direction.offset();
+        let mut pushed: HashSet<EntityId> = HashSet::from([id]);
+        let mut order = vec![id];
+        let mut frontier = VecDeque::from([id]);
+
+        while let Some(current) = frontier.pop_front() {
+            for cell in self.entities[current].cells() {
+                let Some(blocker) = self.entity_at(&(cell + offset)) else {
+                    continue;
+                };
+
+                if pushed.contains(&blocker) {
+                    continue;
+                }
+
+                if !self.entities[blocker].movable {
+                    return None;
+                }
+
+                pushed.insert(blocker);
+                order.push(blocker);
+                frontier.push_back(blocker);
+            }
+        }
+
+        Some(order)
+    }
+
+    /// Moves every entity in `ids` one step in `direction`. `ids` should come
+    /// from `push_set`, which guarantees the destination cells are free.
+    pub fn commit(&mut self, ids: &[EntityId], direction: Direction) {
+        let offset: Coordinate<i32> = direction.offset();
+
+        for &id in ids {
+            for cell in self.entities[id].cells() {
+                self.occupancy.remove(&cell);
+            }
+        }
+
+        for &id in ids {
+            self.entities[id].position += offset;
+
+            for cell in self.entities[id].cells() {
+                self.occupancy.insert(cell, id);
+            }
+        }
+    }
+
+    /// Pushes `id` one step in `direction` along with everything in its way.
+    /// Returns false, changing nothing, if the push is blocked.
+    pub fn push(&mut self, id: EntityId, direction: Direction) -> bool {
+        match self.push_set(id, direction) {
+            Some(ids) => {
+                self.commit(&ids, direction);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    fn warehouse() -> (EntityGrid<char>, EntityId) {
+        let mut grid = EntityGrid::new();
+        let wide = vec![Coordinate { x: 0, y: 0 }, Coordinate { x: 1, y: 0 }];
+
+        let robot = grid
+            .add(
+                '@',
+                Coordinate { x: 2, y: 3 },
+                vec![Coordinate { x: 0, y: 0 }],
+                true,
+            )
+            .unwrap();
+        grid.add('[', Coordinate { x: 1, y: 2 }, wide.clone(), true)
+            .unwrap();
+        grid.add('[', Coordinate { x: 2, y: 1 }, wide.clone(), true)
+            .unwrap();
+        grid.add('[', Coordinate { x: 0, y: 1 }, wide.clone(), true)
+            .unwrap();
+        grid.add(
+            '#',
+            Coordinate { x: 3, y: -1 },
+            vec![Coordinate { x: 0, y: 0 }],
+            false,
+        )
+        .unwrap();
+
+        (grid, robot)
+    }
+
+    #[test]
+    fn test_push_set() {
+        let (grid, robot) = warehouse();
+
+        let mut pushed = grid.push_set(robot, Direction::North).unwrap();
+        pushed.sort();
+        assert_eq!(pushed, vec![0, 1, 2, 3]);
+
+        assert_eq!(grid.push_set(robot, Direction::South), Some(vec![0]));
+        assert_eq!(grid.push_set(4, Direction::South), None);
+    }
+
+    #[test]
+    fn test_push() {
+        let (mut grid, robot) = warehouse();
+
+        assert!(grid.push(robot, Direction::North));
+        assert_eq!(grid.entity(2).position, Coordinate { x: 2, y: 0 });
+        assert_eq!(grid.entity_at(&Coordinate { x: 1, y: 0 }), Some(3));
+        assert_eq!(grid.entity_at(&Coordinate { x: 2, y: 3 }), None);
+
+        // The box at (2, 0) now touches the wall at (3, -1), so nothing moves.
+        let before: Vec<Coordinate<i32>> = grid.entities().map(|(_, e)| e.position).collect();
+        assert!(!grid.push(robot, Direction::North));
+        let after: Vec<Coordinate<i32>> = grid.entities().map(|(_, e)| e.position).collect();
+        assert_eq!(before, after);
+
+        assert!(grid.push(1, Direction::West));
+        assert!(grid.push(1, Direction::East));
+        assert_eq!(grid.entity_at(&Coordinate { x: 2, y: 1 }), Some(1));
+    }
+
+    #[test]
+    fn test_overlap() {
+        let (mut grid, _) = warehouse();
+
+        assert!(grid
+            .add(
+                'O',
+                Coordinate { x: 3, y: 1 },
+                vec![Coordinate { x: 0, y: 0 }],
+                true
+            )
+            .is_err());
+    }
+}
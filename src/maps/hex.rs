@@ -0,0 +1,306 @@
+use std::ops::{Add, Sub};
+
+use num::{Integer, Signed, ToPrimitive};
+
+use super::Direction;
+
+/// Which way the hexagons are laid out. Pointy-top grids have rows and step
+/// `East`/`West` plus the four diagonals; flat-top grids have columns and
+/// step `North`/`South` plus the four diagonals.
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub enum HexOrientation {
+    PointyTop,
+    FlatTop,
+}
+
+impl HexOrientation {
+    pub fn directions(&self) -> [Direction; 6] {
+        use Direction::*;
+
+        match self {
+            HexOrientation::PointyTop => [East, NorthEast, NorthWest, West, SouthWest, SouthEast],
+            HexOrientation::FlatTop => [SouthEast, NorthEast, North, NorthWest, SouthWest, South],
+        }
+    }
+
+    fn offset<T: Integer + Signed + Copy>(&self, direction: Direction) -> Option<Hex<T>> {
+        let index = self.directions().iter().position(|&d| d == direction)?;
+        let (q, r) = [(1, 0), (1, -1), (0, -1), (-1, 0), (-1, 1), (0, 1)][index];
+
+        Some(Hex {
+            q: Self::unit(q),
+            r: Self::unit(r),
+        })
+    }
+
+    fn unit<T: Integer + Signed + Copy>(value: i8) -> T {
+        match value {
+            1 => T::one(),
+            -1 => -T::one(),
+            _ => T::zero(),
+        }
+    }
+
+    /// Parses a list of steps such as `ne,ne,s,s` or `esenee`. Separators are
+    /// optional; two-letter steps are preferred over one-letter steps.
+    pub fn parse_steps(&self, input: &str) -> Result<Vec<Direction>, String> {
+        use Direction::*;
+
+        let parse_step = |step: &str| {
+            match step {
+                "n" => Some(North),
+                "ne" => Some(NorthEast),
+                "e" => Some(East),
+                "se" => Some(SouthEast),
+                "s" => Some(South),
+                "sw" => Some(SouthWest),
+                "w" => Some(West),
+                "nw" => Some(NorthWest),
+                _ => None,
+            }
+            .filter(|direction| self.directions().contains(direction))
+        };
+
+        let characters: Vec<char> = input
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != ',')
+            .collect();
+
+        let mut steps: Vec<Direction> = Vec::new();
+        let mut index = 0;
+
+        while index < characters.len() {
+            let pair: String = characters[index..(index + 2).min(characters.len())]
+                .iter()
+                .collect();
+
+            if let Some(direction) = parse_step(&pair).filter(|_| pair.len() == 2) {
+                steps.push(direction);
+                index += 2;
+            } else if let Some(direction) = parse_step(&characters[index].to_string()) {
+                steps.push(direction);
+                index += 1;
+            } else {
+                return Err(format!(
+                    "Invalid {:?} step at position {index}: '{}'",
+                    self, characters[index]
+                ));
+            }
+        }
+
+        Ok(steps)
+    }
+}
+
+/// A hexagon in axial coordinates. The third cube coordinate is implied by
+/// `q + r + s = 0`.
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub struct Hex<T> {
+    pub q: T,
+    pub r: T,
+}
+
+/// A hexagon in cube coordinates, where `q + r + s = 0`.
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub struct CubeHex<T> {
+    pub q: T,
+    pub r: T,
+    pub s: T,
+}
+
+impl<T: Integer + Signed + Copy> From<Hex<T>> for CubeHex<T> {
+    fn from(hex: Hex<T>) -> Self {
+        CubeHex {
+            q: hex.q,
+            r: hex.r,
+            s: -hex.q - hex.r,
+        }
+    }
+}
+
+impl<T> From<CubeHex<T>> for Hex<T> {
+    fn from(cube: CubeHex<T>) -> Self {
+        Hex {
+            q: cube.q,
+            r: cube.r,
+        }
+    }
+}
+
+impl<T: Add<Output = T>> Add for Hex<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self {
+            q: self.q + other.q,
+            r: self.r + other.r,
+        }
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Hex<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self {
+            q: self.q - other.q,
+            r: self.r - other.r,
+        }
+    }
+}
+
+impl<T: Integer + Signed + Copy> Hex<T> {
+    pub fn origin() -> Self {
+        Hex {
+            q: T::zero(),
+            r: T::zero(),
+        }
+    }
+
+    pub fn s(&self) -> T {
+        -self.q - self.r
+    }
+
+    /// The adjacent hexagon in `direction`, or `None` if that direction does
+    /// not exist for the given orientation.
+    pub fn neighbour(&self, direction: Direction, orientation: HexOrientation) -> Option<Self> {
+        orientation.offset(direction).map(|offset| *self + offset)
+    }
+
+    pub fn neighbours(&self) -> Vec<Self> {
+        HexOrientation::PointyTop
+            .directions()
+            .iter()
+            .filter_map(|&direction| self.neighbour(direction, HexOrientation::PointyTop))
+            .collect()
+    }
+
+    pub fn walk(&self, steps: &[Direction], orientation: HexOrientation) -> Option<Self> {
+        steps.iter().try_fold(*self, |hex, &direction| {
+            hex.neighbour(direction, orientation)
+        })
+    }
+
+    pub fn distance(&self, other: &Self) -> T {
+        let difference = *self - *other;
+
+        (difference.q.abs() + difference.r.abs() + difference.s().abs()) / (T::one() + T::one())
+    }
+
+    /// Every hexagon exactly `radius` steps away, in walking order.
+    pub fn ring(&self, radius: T) -> Vec<Self> {
+        if radius.is_zero() {
+            return vec![*self];
+        }
+
+        let orientation = HexOrientation::PointyTop;
+        let mut ring: Vec<Self> = Vec::new();
+        let mut current = *self;
+
+        let mut step = T::zero();
+        while step < radius {
+            current = current
+                .neighbour(Direction::SouthWest, orientation)
+                .unwrap();
+            step = step + T::one();
+        }
+
+        for direction in orientation.directions() {
+            let mut step = T::zero();
+            while step < radius {
+                ring.push(current);
+                current = current.neighbour(direction, orientation).unwrap();
+                step = step + T::one();
+            }
+        }
+
+        ring
+    }
+
+    /// Every hexagon within `radius` steps, ordered by increasing distance.
+    pub fn spiral(&self, radius: T) -> Vec<Self> {
+        let mut hexes: Vec<Self> = Vec::new();
+        let mut current = T::zero();
+
+        while current <= radius {
+            hexes.extend(self.ring(current));
+            current = current + T::one();
+        }
+
+        hexes
+    }
+}
+
+impl<T: ToPrimitive + Copy> Hex<T> {
+    /// The centre of this hexagon in pixel space, where `size` is the
+    /// distance from the centre to a corner.
+    pub fn to_pixel(&self, orientation: HexOrientation, size: f64) -> (f64, f64) {
+        let q = self.q.to_f64().unwrap_or_default();
+        let r = self.r.to_f64().unwrap_or_default();
+        let root_three = 3f64.sqrt();
+
+        match orientation {
+            HexOrientation::PointyTop => (
+                size * (root_three * q + root_three / 2.0 * r),
+                size * (1.5 * r),
+            ),
+            HexOrientation::FlatTop => (
+                size * (1.5 * q),
+                size * (root_three / 2.0 * q + root_three * r),
+            ),
+        }
+    }
+}
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    fn distance_after(steps: &str, orientation: HexOrientation) -> i32 {
+        let steps = orientation.parse_steps(steps).unwrap();
+        let origin: Hex<i32> = Hex::origin();
+
+        origin.walk(&steps, orientation).unwrap().distance(&origin)
+    }
+
+    #[test]
+    fn test_flat_top_distance() {
+        use HexOrientation::FlatTop;
+
+        assert_eq!(distance_after("ne,ne,ne", FlatTop), 3);
+        assert_eq!(distance_after("ne,ne,sw,sw", FlatTop), 0);
+        assert_eq!(distance_after("ne,ne,s,s", FlatTop), 2);
+        assert_eq!(distance_after("se,sw,se,sw,sw", FlatTop), 3);
+    }
+
+    #[test]
+    fn test_pointy_top_steps() {
+        use HexOrientation::PointyTop;
+
+        let steps = PointyTop.parse_steps("esenee").unwrap();
+        assert_eq!(steps.len(), 4);
+        assert_eq!(distance_after("nwwswee", PointyTop), 0);
+        assert_eq!(distance_after("esew", PointyTop), 1);
+        assert!(PointyTop.parse_steps("n").is_err());
+    }
+
+    #[test]
+    fn test_rings() {
+        let origin: Hex<i32> = Hex::origin();
+
+        assert_eq!(origin.ring(0).len(), 1);
+        assert_eq!(origin.ring(1).len(), 6);
+        assert_eq!(origin.ring(3).len(), 18);
+        assert!(origin.ring(3).iter().all(|hex| hex.distance(&origin) == 3));
+        assert_eq!(origin.spiral(2).len(), 19);
+    }
+
+    #[test]
+    fn test_cube_conversion() {
+        let hex = Hex { q: 2, r: -5 };
+        let cube = CubeHex::from(hex);
+
+        assert_eq!(cube.q + cube.r + cube.s, 0);
+        assert_eq!(Hex::from(cube), hex);
+    }
+}
@@ -0,0 +1,246 @@
+use std::{
+    fmt::{Debug, Display},
+    ops::{Add, AddAssign, Div, Index, IndexMut, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+use num::Integer;
+
+use super::Coordinate;
+
+#[derive(PartialEq, Eq, Hash, Copy, Clone, PartialOrd, Ord)]
+pub struct Point<T, const N: usize>(pub [T; N]);
+
+impl<T, const N: usize> Point<T, N> {
+    pub fn new(components: [T; N]) -> Self {
+        Self(components)
+    }
+}
+
+impl<T: FromStr, const N: usize> FromStr for Point<T, N> {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parts: Vec<&str> = s.split(',').collect();
+
+        if parts.len() != N {
+            return Err(format!(
+                "Input must contain {N} comma-separated values, found {}",
+                parts.len()
+            ));
+        }
+
+        let mut components: Vec<T> = Vec::with_capacity(N);
+
+        for (index, part) in parts.iter().enumerate() {
+            components.push(
+                part.trim()
+                    .parse::<T>()
+                    .map_err(|_| format!("Failed to parse component {index}: '{part}'"))?,
+            );
+        }
+
+        match components.try_into() {
+            Ok(components) => Ok(Self(components)),
+            Err(_) => unreachable!(),
+        }
+    }
+}
+
+impl<T: Display, const N: usize> Debug for Point<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl<T: Display, const N: usize> Display for Point<T, N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let components: Vec<String> = self.0.iter().map(|c| c.to_string()).collect();
+
+        write!(f, "({})", components.join(", "))
+    }
+}
+
+impl<T, const N: usize> Index<usize> for Point<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.0[index]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for Point<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut Self::Output {
+        &mut self.0[index]
+    }
+}
+
+impl<T: Add<Output = T> + Copy, const N: usize> Add for Point<T, N> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self(std::array::from_fn(|i| self.0[i] + other.0[i]))
+    }
+}
+
+impl<T: Sub<Output = T> + Copy, const N: usize> Sub for Point<T, N> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self(std::array::from_fn(|i| self.0[i] - other.0[i]))
+    }
+}
+
+impl<T: AddAssign + Copy, const N: usize> AddAssign for Point<T, N> {
+    fn add_assign(&mut self, rhs: Self) {
+        for (component, other) in self.0.iter_mut().zip(rhs.0) {
+            *component += other;
+        }
+    }
+}
+
+impl<T: SubAssign + Copy, const N: usize> SubAssign for Point<T, N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        for (component, other) in self.0.iter_mut().zip(rhs.0) {
+            *component -= other;
+        }
+    }
+}
+
+impl<T: Mul<Output = T> + Copy, const N: usize> Mul<T> for Point<T, N> {
+    type Output = Self;
+
+    fn mul(self, scalar: T) -> Self::Output {
+        Self(self.0.map(|c| c * scalar))
+    }
+}
+
+impl<T: Div<Output = T> + Copy, const N: usize> Div<T> for Point<T, N> {
+    type Output = Self;
+
+    fn div(self, scalar: T) -> Self::Output {
+        Self(self.0.map(|c| c / scalar))
+    }
+}
+
+impl<T: Neg<Output = T>, const N: usize> Neg for Point<T, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(self.0.map(|c| -c))
+    }
+}
+
+impl<T> From<Coordinate<T>> for Point<T, 2> {
+    fn from(coordinate: Coordinate<T>) -> Self {
+        Self([coordinate.x, coordinate.y])
+    }
+}
+
+impl<T> From<Point<T, 2>> for Coordinate<T> {
+    fn from(point: Point<T, 2>) -> Self {
+        let [x, y] = point.0;
+
+        Coordinate { x, y }
+    }
+}
+
+impl<T: Integer + Copy, const N: usize> Point<T, N> {
+    pub fn manhattan(&self, other: &Self) -> T {
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .fold(T::zero(), |total, (&a, &b)| {
+                total + if a > b { a - b } else { b - a }
+            })
+    }
+
+    pub fn chebyshev(&self, other: &Self) -> T {
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .fold(T::zero(), |largest, (&a, &b)| {
+                largest.max(if a > b { a - b } else { b - a })
+            })
+    }
+
+    /// The `2 * N` points that differ from this one by one along a single axis.
+    pub fn neighbours(&self) -> Vec<Self> {
+        let mut neighbours = Vec::with_capacity(2 * N);
+
+        for axis in 0..N {
+            let mut lower = *self;
+            lower.0[axis] = lower.0[axis] - T::one();
+            neighbours.push(lower);
+
+            let mut upper = *self;
+            upper.0[axis] = upper.0[axis] + T::one();
+            neighbours.push(upper);
+        }
+
+        neighbours
+    }
+
+    /// The `3^N - 1` points in the surrounding Moore neighbourhood.
+    pub fn extended_neighbours(&self) -> Vec<Self> {
+        let mut neighbours = vec![*self];
+
+        for axis in 0..N {
+            neighbours = neighbours
+                .into_iter()
+                .flat_map(|point| {
+                    let mut lower = point;
+                    lower.0[axis] = lower.0[axis] - T::one();
+
+                    let mut upper = point;
+                    upper.0[axis] = upper.0[axis] + T::one();
+
+                    [lower, point, upper]
+                })
+                .collect();
+        }
+
+        neighbours.retain(|point| point != self);
+
+        neighbours
+    }
+}
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let point: Point<i32, 3> = "1, -2,3".parse().unwrap();
+
+        assert_eq!(point, Point([1, -2, 3]));
+        assert!("1,2".parse::<Point<i32, 3>>().is_err());
+        assert!("1,a,3".parse::<Point<i32, 3>>().is_err());
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point([1i64, 2, 3, 4]);
+        let b = Point([4i64, 3, 2, 1]);
+
+        assert_eq!(a + b, Point([5, 5, 5, 5]));
+        assert_eq!(a - b, Point([-3, -1, 1, 3]));
+        assert_eq!(-a * 2, Point([-2, -4, -6, -8]));
+        assert_eq!(a.manhattan(&b), 8);
+        assert_eq!(a.chebyshev(&b), 3);
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let origin = Point([0i32; 3]);
+
+        assert_eq!(origin.neighbours().len(), 6);
+        assert_eq!(origin.extended_neighbours().len(), 26);
+        assert_eq!(Point([0i32; 4]).extended_neighbours().len(), 80);
+        assert!(origin
+            .extended_neighbours()
+            .iter()
+            .all(|n| origin.chebyshev(n) == 1));
+    }
+}
@@ -0,0 +1,281 @@
+use std::collections::{HashSet, VecDeque};
+
+use super::{Coordinate, Direction, MapData, Topology};
+use crate::geometry::trace_boundary;
+
+/// Which cells count as touching when grouping cells into regions.
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub enum Connectivity {
+    /// Cells sharing an edge.
+    Four,
+    /// Cells sharing an edge or a corner.
+    Eight,
+}
+
+impl Connectivity {
+    pub fn directions(&self) -> &'static [Direction] {
+        use Direction::*;
+
+        match self {
+            Connectivity::Four => &[North, East, South, West],
+            Connectivity::Eight => &[
+                North, NorthEast, East, SouthEast, South, SouthWest, West, NorthWest,
+            ],
+        }
+    }
+}
+
+/// A connected group of cells found by `MapData::regions`. It keeps the
+/// map's size and topology, so cells across a wrapping edge count as
+/// touching.
+pub struct Region {
+    pub label: usize,
+    pub cells: Vec<Coordinate<usize>>,
+    members: HashSet<Coordinate<usize>>,
+    topology: Topology,
+    width: usize,
+    height: usize,
+}
+
+impl Region {
+    fn has(&self, x: i64, y: i64) -> bool {
+        self.topology
+            .resolve(Coordinate { x, y }, self.width as i64, self.height as i64)
+            .is_some_and(|resolved| {
+                self.members.contains(&Coordinate {
+                    x: resolved.x as usize,
+                    y: resolved.y as usize,
+                })
+            })
+    }
+
+    pub fn contains(&self, coordinate: &Coordinate<usize>) -> bool {
+        self.members.contains(coordinate)
+    }
+
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The number of cell edges that border something outside the region.
+    pub fn perimeter(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|cell| {
+                let (x, y) = (cell.x as i64, cell.y as i64);
+
+                [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                    .into_iter()
+                    .filter(|&(x, y)| !self.has(x, y))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// The number of straight fence sections around the region, including
+    /// around any holes. A polygon has as many sides as corners, so this
+    /// counts the convex and concave corners of every cell.
+    pub fn sides(&self) -> usize {
+        self.cells
+            .iter()
+            .map(|cell| {
+                let (x, y) = (cell.x as i64, cell.y as i64);
+
+                [(1, 1), (1, -1), (-1, 1), (-1, -1)]
+                    .into_iter()
+                    .filter(|&(dx, dy)| {
+                        let horizontal = self.has(x + dx, y);
+                        let vertical = self.has(x, y + dy);
+                        let diagonal = self.has(x + dx, y + dy);
+
+                        (!horizontal && !vertical) || (horizontal && vertical && !diagonal)
+                    })
+                    .count()
+            })
+            .sum()
+    }
+
+    /// The corners of the region's outer boundary, ignoring holes. See
+    /// `geometry::trace_boundary`. This works in map coordinates, so it
+    /// ignores wrapping edges.
+    pub fn outline(&self) -> Vec<Coordinate<i64>> {
+        let cells = self
+            .cells
+            .iter()
+            .map(|cell| Coordinate {
+                x: cell.x as i64,
+                y: cell.y as i64,
+            })
+            .collect();
+
+        trace_boundary(&cells)
+    }
+
+    /// The inclusive `(min, max)` corners of the region.
+    pub fn bounding_box(&self) -> (Coordinate<usize>, Coordinate<usize>) {
+        self.cells
+            .iter()
+            .skip(1)
+            .fold((self.cells[0], self.cells[0]), |(min, max), cell| {
+                (
+                    Coordinate {
+                        x: min.x.min(cell.x),
+                        y: min.y.min(cell.y),
+                    },
+                    Coordinate {
+                        x: max.x.max(cell.x),
+                        y: max.y.max(cell.y),
+                    },
+                )
+            })
+    }
+}
+
+/// Every region of a map, along with a map giving the region label of each
+/// cell.
+pub struct Regions {
+    pub labels: MapData<usize>,
+    pub regions: Vec<Region>,
+}
+
+impl<T> MapData<T>
+where
+    T: Copy,
+{
+    /// Groups touching cells for which `same` holds into labelled regions.
+    /// Labels are assigned in reading order and index into `regions`.
+    pub fn regions_by<F>(&self, connectivity: Connectivity, same: F) -> Regions
+    where
+        F: Fn(T, T) -> bool,
+    {
+        let mut labels: MapData<Option<usize>> = MapData::new(
+            self.width,
+            self.height,
+            vec![vec![None; self.width]; self.height],
+        );
+        let mut regions: Vec<Region> = vec![];
+
+        for (start, _) in self.enumerate::<usize>() {
+            if labels.get(&start) != Some(None) {
+                continue;
+            }
+
+            let label = regions.len();
+            let mut cells = vec![];
+            let mut frontier = VecDeque::from([start]);
+            labels.set(&start, Some(label));
+
+            while let Some(current) = frontier.pop_front() {
+                cells.push(current);
+                let value = self.get(&current).unwrap();
+
+                for &direction in connectivity.directions() {
+                    let Some(next) = self.neighbour(&current, direction) else {
+                        continue;
+                    };
+
+                    if labels.get(&next) == Some(None) && same(value, self.get(&next).unwrap()) {
+                        labels.set(&next, Some(label));
+                        frontier.push_back(next);
+                    }
+                }
+            }
+
+            regions.push(Region {
+                label,
+                members: cells.iter().copied().collect(),
+                cells,
+                topology: self.topology,
+                width: self.width,
+                height: self.height,
+            });
+        }
+
+        let labels = MapData::new(
+            self.width,
+            self.height,
+            (0..self.height)
+                .map(|y| {
+                    (0..self.width)
+                        .map(|x| labels.get(&Coordinate { x, y }).flatten().unwrap())
+                        .collect()
+                })
+                .collect(),
+        );
+
+        Regions { labels, regions }
+    }
+
+    /// Groups touching cells with equal values into labelled regions.
+    pub fn regions(&self, connectivity: Connectivity) -> Regions
+    where
+        T: PartialEq,
+    {
+        self.regions_by(connectivity, |a, b| a == b)
+    }
+}
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    const X_AND_O: &str = "OOOOO
+OXOXO
+OOOOO
+OXOXO
+OOOOO";
+
+    #[test]
+    fn test_regions() {
+        let map: MapData<char> = MapData::new_from_str(X_AND_O).unwrap();
+        let Regions { labels, regions } = map.regions(Connectivity::Four);
+
+        assert_eq!(regions.len(), 5);
+        assert_eq!(labels.get(&Coordinate { x: 3usize, y: 1 }), Some(2));
+
+        let outer = &regions[0];
+        assert_eq!(
+            (outer.area(), outer.perimeter(), outer.sides()),
+            (21, 36, 20)
+        );
+        assert_eq!(
+            outer.bounding_box(),
+            (Coordinate { x: 0, y: 0 }, Coordinate { x: 4, y: 4 })
+        );
+        assert_eq!((regions[1].perimeter(), regions[1].sides()), (4, 4));
+        assert_eq!(outer.outline().len(), 4);
+    }
+
+    #[test]
+    fn test_regions_by() {
+        let map: MapData<char> = MapData::new_from_str("AB.\nBA.\n..C").unwrap();
+
+        let letters = map.regions_by(Connectivity::Eight, |a, b| {
+            a.is_alphabetic() == b.is_alphabetic()
+        });
+        assert_eq!(letters.regions.len(), 2);
+        assert_eq!(letters.regions[0].area(), 5);
+        assert_eq!(letters.regions[0].sides(), 8);
+
+        assert_eq!(map.regions(Connectivity::Four).regions.len(), 7);
+        assert_eq!(map.regions(Connectivity::Eight).regions.len(), 4);
+    }
+
+    #[test]
+    fn test_wrapping_regions() {
+        let map: MapData<char> = MapData::new_from_str("A..A\n....\n....").unwrap();
+
+        let bounded = map.regions(Connectivity::Four);
+        assert_eq!(bounded.regions[0].area(), 1);
+        assert_eq!(bounded.regions[0].perimeter(), 4);
+
+        let wrapped = map
+            .with_topology(Topology::WrapX)
+            .regions(Connectivity::Four);
+        let seam = &wrapped.regions[wrapped.labels.get(&Coordinate { x: 0usize, y: 0 }).unwrap()];
+
+        assert_eq!(seam.area(), 2);
+        assert_eq!(seam.perimeter(), 6);
+        assert_eq!(seam.sides(), 4);
+    }
+}
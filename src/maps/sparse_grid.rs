@@ -0,0 +1,342 @@
+use std::{collections::HashMap, fmt::Display, hash::Hash};
+
+use num::{Integer, NumCast};
+
+use super::{Coordinate, MapData};
+
+/// An unbounded grid that only stores cells which have been set. Every other
+/// cell reads as `default`. The bounding box of the stored cells is tracked as
+/// cells are inserted and removed, and always includes any fixed extent given
+/// by `with_bounds`.
+#[derive(Clone)]
+pub struct SparseGrid<T, C = i32> {
+    cells: HashMap<Coordinate<C>, T>,
+    default: T,
+    extent: Option<(Coordinate<C>, Coordinate<C>)>,
+    bounds: Option<(Coordinate<C>, Coordinate<C>)>,
+}
+
+impl<T, C> SparseGrid<T, C>
+where
+    T: Copy,
+    C: Integer + Hash + Copy + NumCast,
+{
+    pub fn new(default: T) -> Self {
+        Self {
+            cells: HashMap::new(),
+            default,
+            extent: None,
+            bounds: None,
+        }
+    }
+
+    /// Fixes the inclusive `(min, max)` corners as part of the bounds, even
+    /// where those cells only hold the default.
+    pub fn with_bounds(mut self, min: Coordinate<C>, max: Coordinate<C>) -> Self {
+        self.extent = Self::expand_bounds(Self::expand_bounds(None, &min), &max);
+        self.bounds = self.cells.keys().fold(self.extent, Self::expand_bounds);
+
+        self
+    }
+
+    pub fn default_value(&self) -> T {
+        self.default
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The inclusive `(min, max)` corners of the stored cells and the fixed
+    /// extent, if any.
+    pub fn bounds(&self) -> Option<(Coordinate<C>, Coordinate<C>)> {
+        self.bounds
+    }
+
+    pub fn width(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.x - min.x).to_usize().unwrap() + 1)
+    }
+
+    pub fn height(&self) -> usize {
+        self.bounds
+            .map_or(0, |(min, max)| (max.y - min.y).to_usize().unwrap() + 1)
+    }
+
+    pub fn contains(&self, coordinate: &Coordinate<C>) -> bool {
+        self.cells.contains_key(coordinate)
+    }
+
+    pub fn get(&self, coordinate: &Coordinate<C>) -> T {
+        self.cells.get(coordinate).copied().unwrap_or(self.default)
+    }
+
+    fn expand_bounds(
+        bounds: Option<(Coordinate<C>, Coordinate<C>)>,
+        coordinate: &Coordinate<C>,
+    ) -> Option<(Coordinate<C>, Coordinate<C>)> {
+        Some(match bounds {
+            Some((min, max)) => (
+                Coordinate {
+                    x: min.x.min(coordinate.x),
+                    y: min.y.min(coordinate.y),
+                },
+                Coordinate {
+                    x: max.x.max(coordinate.x),
+                    y: max.y.max(coordinate.y),
+                },
+            ),
+            None => (*coordinate, *coordinate),
+        })
+    }
+
+    pub fn insert(&mut self, coordinate: Coordinate<C>, value: T) -> Option<T> {
+        self.bounds = Self::expand_bounds(self.bounds, &coordinate);
+
+        self.cells.insert(coordinate, value)
+    }
+
+    pub fn remove(&mut self, coordinate: &Coordinate<C>) -> Option<T> {
+        let removed = self.cells.remove(coordinate)?;
+
+        if let Some((min, max)) = self.bounds {
+            if coordinate.x == min.x
+                || coordinate.x == max.x
+                || coordinate.y == min.y
+                || coordinate.y == max.y
+            {
+                self.bounds = self.cells.keys().fold(self.extent, Self::expand_bounds);
+            }
+        }
+
+        Some(removed)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Coordinate<C>, &T)> {
+        self.cells.iter()
+    }
+
+    /// The four orthogonal neighbours of `coordinate` with their values,
+    /// including cells that only hold the default.
+    pub fn neighbours(&self, coordinate: &Coordinate<C>) -> Vec<(Coordinate<C>, T)> {
+        coordinate
+            .neighbours()
+            .into_iter()
+            .map(|neighbour| (neighbour, self.get(&neighbour)))
+            .collect()
+    }
+
+    /// The orthogonal neighbours of `coordinate` that have a stored value.
+    pub fn occupied_neighbours(&self, coordinate: &Coordinate<C>) -> Vec<Coordinate<C>> {
+        coordinate
+            .neighbours()
+            .into_iter()
+            .filter(|neighbour| self.contains(neighbour))
+            .collect()
+    }
+
+    /// Draws every cell in the inclusive range `min..=max`, one line per row.
+    pub fn render_within<F>(&self, min: Coordinate<C>, max: Coordinate<C>, render_fn: F) -> String
+    where
+        F: Fn(Coordinate<C>, T) -> char,
+    {
+        let mut lines: Vec<String> = Vec::new();
+
+        let mut y = min.y;
+        while y <= max.y {
+            let mut line = String::new();
+
+            let mut x = min.x;
+            while x <= max.x {
+                let coordinate = Coordinate { x, y };
+                line.push(render_fn(coordinate, self.get(&coordinate)));
+
+                x = x + C::one();
+            }
+
+            lines.push(line);
+            y = y + C::one();
+        }
+
+        lines.join("\n")
+    }
+
+    /// Draws every cell within the current bounds.
+    pub fn render<F>(&self, render_fn: F) -> String
+    where
+        F: Fn(Coordinate<C>, T) -> char,
+    {
+        match self.bounds {
+            Some((min, max)) => self.render_within(min, max, render_fn),
+            None => String::new(),
+        }
+    }
+
+    /// Stores every cell of `map` that is not equal to `default`. The bounds
+    /// keep the full size of `map`, so `to_map_data` gives back the same map.
+    pub fn from_map_data(map: &MapData<T>, default: T) -> Self
+    where
+        T: PartialEq,
+    {
+        let mut grid = Self::new(default);
+
+        if map.width > 0 && map.height > 0 {
+            grid = grid.with_bounds(
+                Coordinate {
+                    x: C::zero(),
+                    y: C::zero(),
+                },
+                Coordinate {
+                    x: C::from(map.width - 1).unwrap(),
+                    y: C::from(map.height - 1).unwrap(),
+                },
+            );
+        }
+
+        for (coordinate, &value) in map.enumerate::<usize>() {
+            if value != default {
+                grid.insert(
+                    Coordinate {
+                        x: C::from(coordinate.x).unwrap(),
+                        y: C::from(coordinate.y).unwrap(),
+                    },
+                    value,
+                );
+            }
+        }
+
+        grid
+    }
+
+    /// Copies the inclusive range `min..=max` into a dense map, with `min`
+    /// becoming `(0, 0)`.
+    pub fn to_map_data_within(&self, min: Coordinate<C>, max: Coordinate<C>) -> MapData<T> {
+        let width = (max.x - min.x).to_usize().unwrap() + 1;
+        let height = (max.y - min.y).to_usize().unwrap() + 1;
+
+        let data: Vec<Vec<T>> = (0..height)
+            .map(|y| {
+                (0..width)
+                    .map(|x| {
+                        self.get(&Coordinate {
+                            x: min.x + C::from(x).unwrap(),
+                            y: min.y + C::from(y).unwrap(),
+                        })
+                    })
+                    .collect()
+            })
+            .collect();
+
+        MapData::new(width, height, data)
+    }
+
+    /// Copies the current bounds into a dense map, with the top-left corner of
+    /// the bounds becoming `(0, 0)`.
+    pub fn to_map_data(&self) -> MapData<T> {
+        match self.bounds {
+            Some((min, max)) => self.to_map_data_within(min, max),
+            None => MapData::new(0usize, 0usize, vec![]),
+        }
+    }
+}
+
+impl<T, C> Display for SparseGrid<T, C>
+where
+    T: Copy + Into<char>,
+    C: Integer + Hash + Copy + NumCast,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.render(|_, value| value.into()))
+    }
+}
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    #[test]
+    fn test_bounds() {
+        let mut grid: SparseGrid<char> = SparseGrid::new('.');
+
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert(Coordinate { x: -2, y: 3 }, '#');
+        grid.insert(Coordinate { x: 4, y: -1 }, '#');
+        grid.insert(Coordinate { x: 1, y: 1 }, '#');
+
+        assert_eq!(
+            grid.bounds(),
+            Some((Coordinate { x: -2, y: -1 }, Coordinate { x: 4, y: 3 }))
+        );
+        assert_eq!((grid.width(), grid.height()), (7, 5));
+
+        grid.remove(&Coordinate { x: 4, y: -1 });
+
+        assert_eq!(
+            grid.bounds(),
+            Some((Coordinate { x: -2, y: 1 }, Coordinate { x: 1, y: 3 }))
+        );
+        assert_eq!(grid.get(&Coordinate { x: 4, y: -1 }), '.');
+    }
+
+    #[test]
+    fn test_render() {
+        let mut grid: SparseGrid<char> = SparseGrid::new('.');
+        grid.insert(Coordinate { x: 0, y: 0 }, '#');
+        grid.insert(Coordinate { x: 2, y: 1 }, '#');
+
+        assert_eq!(grid.to_string(), "#..\n..#");
+        assert_eq!(
+            grid.render_within(
+                Coordinate { x: -1, y: 0 },
+                Coordinate { x: 1, y: 0 },
+                |_, v| v
+            ),
+            ".#."
+        );
+    }
+
+    #[test]
+    fn test_map_data_round_trip() {
+        let map: MapData<char> = MapData::new_from_str("#..\n.#.\n..#").unwrap();
+        let grid: SparseGrid<char> = SparseGrid::from_map_data(&map, '.');
+
+        assert_eq!(grid.len(), 3);
+        assert_eq!(
+            grid.occupied_neighbours(&Coordinate { x: 1, y: 0 }).len(),
+            2
+        );
+
+        let round_trip = grid.to_map_data();
+
+        assert_eq!(round_trip.width, 3);
+        assert_eq!(round_trip.get(&Coordinate { x: 2usize, y: 2 }), Some('#'));
+        assert_eq!(round_trip.get(&Coordinate { x: 1usize, y: 2 }), Some('.'));
+    }
+
+    #[test]
+    fn test_map_data_round_trip_default_edges() {
+        let map: MapData<char> = MapData::new_from_str(
+            "....
+.#..
+....",
+        )
+        .unwrap();
+        let mut grid: SparseGrid<char> = SparseGrid::from_map_data(&map, '.');
+
+        assert_eq!(grid.len(), 1);
+        assert_eq!((grid.width(), grid.height()), (4, 3));
+
+        let round_trip = grid.to_map_data();
+
+        assert_eq!((round_trip.width, round_trip.height), (4, 3));
+        assert_eq!(round_trip.get(&Coordinate { x: 1usize, y: 1 }), Some('#'));
+
+        grid.remove(&Coordinate { x: 1, y: 1 });
+        assert_eq!((grid.width(), grid.height()), (4, 3));
+    }
+}
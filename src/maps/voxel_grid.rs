@@ -0,0 +1,230 @@
+use std::collections::{HashSet, VecDeque};
+
+use super::Point;
+
+pub type Voxel = Point<i64, 3>;
+
+/// A dense, bounded 3D grid covering `origin` up to (but not including)
+/// `origin + (width, height, depth)`.
+pub struct VoxelGrid<T> {
+    pub origin: Voxel,
+    pub width: usize,
+    pub height: usize,
+    pub depth: usize,
+    data: Vec<T>,
+}
+
+impl<T> VoxelGrid<T>
+where
+    T: Copy,
+{
+    pub fn new(width: usize, height: usize, depth: usize, fill: T) -> Self {
+        Self {
+            origin: Point([0, 0, 0]),
+            width,
+            height,
+            depth,
+            data: vec![fill; width * height * depth],
+        }
+    }
+
+    /// Builds the smallest grid containing every point, with those points set
+    /// to `filled` and everything else set to `empty`.
+    pub fn from_points(points: &[Voxel], filled: T, empty: T) -> Result<Self, String> {
+        let first = points
+            .first()
+            .ok_or_else(|| "Cannot build a voxel grid from no points".to_string())?;
+
+        let (min, max) = points.iter().fold((*first, *first), |(min, max), point| {
+            (
+                Point(std::array::from_fn(|i| min[i].min(point[i]))),
+                Point(std::array::from_fn(|i| max[i].max(point[i]))),
+            )
+        });
+
+        let size = max - min + Point([1, 1, 1]);
+        let mut grid = Self::new(size[0] as usize, size[1] as usize, size[2] as usize, empty);
+        grid.origin = min;
+
+        for point in points {
+            grid.set(point, filled);
+        }
+
+        Ok(grid)
+    }
+
+    fn index_of(&self, point: &Voxel) -> Option<usize> {
+        let offset = *point - self.origin;
+
+        if (0..self.width as i64).contains(&offset[0])
+            && (0..self.height as i64).contains(&offset[1])
+            && (0..self.depth as i64).contains(&offset[2])
+        {
+            let (x, y, z) = (offset[0] as usize, offset[1] as usize, offset[2] as usize);
+
+            return Some((z * self.height + y) * self.width + x);
+        }
+
+        None
+    }
+
+    pub fn contains(&self, point: &Voxel) -> bool {
+        self.index_of(point).is_some()
+    }
+
+    pub fn get(&self, point: &Voxel) -> Option<T> {
+        self.index_of(point).map(|index| self.data[index])
+    }
+
+    /// Stores `value` at `point`, returning the previous value or `None` if
+    /// the point is outside the grid.
+    pub fn set(&mut self, point: &Voxel, value: T) -> Option<T> {
+        let index = self.index_of(point)?;
+
+        Some(std::mem::replace(&mut self.data[index], value))
+    }
+
+    pub fn enumerate(&self) -> impl Iterator<Item = (Voxel, &T)> {
+        self.data.iter().enumerate().map(move |(index, value)| {
+            let x = (index % self.width) as i64;
+            let y = ((index / self.width) % self.height) as i64;
+            let z = (index / (self.width * self.height)) as i64;
+
+            (self.origin + Point([x, y, z]), value)
+        })
+    }
+
+    /// Every voxel reachable from `start` through orthogonal steps onto
+    /// voxels for which `passable` holds.
+    pub fn flood_fill<F>(&self, start: &Voxel, passable: F) -> HashSet<Voxel>
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.flood_fill_from(std::iter::once(*start), passable)
+    }
+
+    fn flood_fill_from<F>(&self, starts: impl Iterator<Item = Voxel>, passable: F) -> HashSet<Voxel>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut visited: HashSet<Voxel> = HashSet::new();
+        let mut queue: VecDeque<Voxel> = VecDeque::new();
+
+        for start in starts {
+            if self.get(&start).is_some_and(|value| passable(&value)) && visited.insert(start) {
+                queue.push_back(start);
+            }
+        }
+
+        while let Some(current) = queue.pop_front() {
+            for neighbour in current.neighbours() {
+                if !visited.contains(&neighbour)
+                    && self.get(&neighbour).is_some_and(|value| passable(&value))
+                {
+                    visited.insert(neighbour);
+                    queue.push_back(neighbour);
+                }
+            }
+        }
+
+        visited
+    }
+
+    /// The number of solid voxel faces that are not shared with another solid
+    /// voxel, including faces on internal air pockets.
+    pub fn surface_area<F>(&self, is_solid: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.enumerate()
+            .filter(|(_, value)| is_solid(value))
+            .map(|(point, _)| {
+                point
+                    .neighbours()
+                    .iter()
+                    .filter(|neighbour| !self.get(neighbour).is_some_and(|value| is_solid(&value)))
+                    .count()
+            })
+            .sum()
+    }
+
+    /// Like `surface_area`, but only counts faces that can be reached from
+    /// outside the grid, so enclosed air pockets are ignored.
+    pub fn exterior_surface_area<F>(&self, is_solid: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        let boundary = self.enumerate().map(|(point, _)| point).filter(|point| {
+            point
+                .neighbours()
+                .iter()
+                .any(|neighbour| !self.contains(neighbour))
+        });
+
+        let exterior = self.flood_fill_from(boundary, |value| !is_solid(value));
+
+        self.enumerate()
+            .filter(|(_, value)| is_solid(value))
+            .map(|(point, _)| {
+                point
+                    .neighbours()
+                    .iter()
+                    .filter(|neighbour| !self.contains(neighbour) || exterior.contains(neighbour))
+                    .count()
+            })
+            .sum()
+    }
+}
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "2,2,2
+1,2,2
+3,2,2
+2,1,2
+2,3,2
+2,2,1
+2,2,3
+2,2,4
+2,2,6
+1,2,5
+3,2,5
+2,1,5
+2,3,5";
+
+    fn parse(data: &str) -> VoxelGrid<bool> {
+        let points: Vec<Voxel> = data.lines().map(|line| line.parse().unwrap()).collect();
+
+        VoxelGrid::from_points(&points, true, false).unwrap()
+    }
+
+    #[test]
+    fn test_from_points() {
+        let grid = parse(EXAMPLE_INPUT);
+
+        assert_eq!(grid.origin, Point([1, 1, 1]));
+        assert_eq!((grid.width, grid.height, grid.depth), (3, 3, 6));
+        assert_eq!(grid.get(&Point([2, 2, 6])), Some(true));
+        assert_eq!(grid.get(&Point([1, 1, 1])), Some(false));
+        assert_eq!(grid.get(&Point([0, 0, 0])), None);
+    }
+
+    #[test]
+    fn test_surface_area() {
+        let grid = parse(EXAMPLE_INPUT);
+
+        assert_eq!(grid.surface_area(|&solid| solid), 64);
+        assert_eq!(grid.exterior_surface_area(|&solid| solid), 58);
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let grid = parse(EXAMPLE_INPUT);
+
+        let pocket = grid.flood_fill(&Point([2, 2, 5]), |&solid| !solid);
+
+        assert_eq!(pocket, HashSet::from([Point([2, 2, 5])]));
+    }
+}
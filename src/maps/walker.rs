@@ -0,0 +1,149 @@
+use std::collections::HashSet;
+
+use super::{Coordinate, Direction, MapData};
+
+/// What a `Walker` should do next, decided by its rule from the cell ahead.
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub enum Move {
+    Forward,
+    Turn(Direction),
+    Stop,
+}
+
+#[derive(PartialEq, Eq, Hash, Copy, Clone, Debug)]
+pub enum WalkOutcome {
+    /// The walker stepped off the edge of the map.
+    Exited,
+    /// The walker returned to a position and direction it had already been
+    /// in, so it will repeat itself forever.
+    Looped,
+    /// The rule returned `Move::Stop`.
+    Stopped,
+}
+
+/// Something moving around a map one cell at a time, such as a guard on
+/// patrol. It remembers every `(position, direction)` it has been in, so
+/// walks that go round in circles are detected.
+pub struct Walker<'a, T> {
+    map: &'a MapData<T>,
+    pub position: Coordinate<usize>,
+    pub direction: Direction,
+    obstacle: Option<(Coordinate<usize>, T)>,
+    visited: HashSet<(Coordinate<usize>, Direction)>,
+}
+
+impl<'a, T> Walker<'a, T>
+where
+    T: Copy,
+{
+    pub fn new(map: &'a MapData<T>, position: Coordinate<usize>, direction: Direction) -> Self {
+        Self {
+            map,
+            position,
+            direction,
+            obstacle: None,
+            visited: HashSet::from([(position, direction)]),
+        }
+    }
+
+    /// Walks as if the cell at `coordinate` held `value`, without touching
+    /// the map.
+    pub fn with_obstacle(mut self, coordinate: Coordinate<usize>, value: T) -> Self {
+        self.obstacle = Some((coordinate, value));
+        self
+    }
+
+    fn cell(&self, coordinate: &Coordinate<usize>) -> Option<T> {
+        match self.obstacle {
+            Some((obstacle, value)) if obstacle == *coordinate => Some(value),
+            _ => self.map.get(coordinate),
+        }
+    }
+
+    /// Applies `rule` to the cell ahead once. Returns the outcome if the walk
+    /// is over.
+    pub fn step<F>(&mut self, rule: F) -> Option<WalkOutcome>
+    where
+        F: Fn(T, Direction) -> Move,
+    {
+        let Some(ahead) = self.map.neighbour(&self.position, self.direction) else {
+            return Some(WalkOutcome::Exited);
+        };
+
+        match rule(self.cell(&ahead).unwrap(), self.direction) {
+            Move::Forward => self.position = ahead,
+            Move::Turn(direction) => self.direction = direction,
+            Move::Stop => return Some(WalkOutcome::Stopped),
+        }
+
+        if !self.visited.insert((self.position, self.direction)) {
+            return Some(WalkOutcome::Looped);
+        }
+
+        None
+    }
+
+    /// Keeps applying `rule` until the walker exits, loops or stops.
+    pub fn walk<F>(&mut self, rule: F) -> WalkOutcome
+    where
+        F: Fn(T, Direction) -> Move,
+    {
+        loop {
+            if let Some(outcome) = self.step(&rule) {
+                return outcome;
+            }
+        }
+    }
+
+    /// Every `(position, direction)` the walker has been in.
+    pub fn visited(&self) -> &HashSet<(Coordinate<usize>, Direction)> {
+        &self.visited
+    }
+
+    /// Every cell the walker has stood on, whichever way it was facing.
+    pub fn visited_cells(&self) -> HashSet<Coordinate<usize>> {
+        self.visited
+            .iter()
+            .map(|(coordinate, _)| *coordinate)
+            .collect()
+    }
+}
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    fn turn_right_at_walls(cell: char, direction: Direction) -> Move {
+        if cell == '#' {
+            Move::Turn(direction.turn_right())
+        } else {
+            Move::Forward
+        }
+    }
+
+    #[test]
+    fn test_walker() {
+        let map: MapData<char> = MapData::new_from_str(".#..\n...#\n....\n..#.").unwrap();
+        let start = Coordinate { x: 1, y: 2 };
+
+        let mut walker = Walker::new(&map, start, Direction::North);
+        assert_eq!(walker.walk(turn_right_at_walls), WalkOutcome::Exited);
+        assert_eq!(walker.visited_cells().len(), 5);
+
+        let mut walker = Walker::new(&map, start, Direction::North)
+            .with_obstacle(Coordinate { x: 0, y: 2 }, '#');
+        assert_eq!(walker.walk(turn_right_at_walls), WalkOutcome::Looped);
+        assert_eq!(map.get(&Coordinate { x: 0usize, y: 2 }), Some('.'));
+
+        let mut walker = Walker::new(&map, start, Direction::North);
+        let outcome = walker.walk(|cell, _| {
+            if cell == '#' {
+                Move::Stop
+            } else {
+                Move::Forward
+            }
+        });
+        assert_eq!(outcome, WalkOutcome::Stopped);
+        assert_eq!(walker.position, Coordinate { x: 1, y: 1 });
+    }
+}
@@ -0,0 +1,289 @@
+use std::{
+    cmp::{Ordering, Reverse},
+    collections::{BinaryHeap, HashMap, HashSet},
+    fmt::{Debug, Display},
+    hash::Hash,
+    str::FromStr,
+};
+
+/// A set of nodes that could not be ordered because they depend on each
+/// other. `cycle` lists one such loop in order, each node having to come
+/// before the next and the last before the first.
+#[derive(PartialEq, Eq, Debug)]
+pub struct CycleError<T> {
+    pub cycle: Vec<T>,
+}
+
+impl<T: Debug> Display for CycleError<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Precedence rules contain a cycle: {:?}", self.cycle)
+    }
+}
+
+impl<T: Debug> std::error::Error for CycleError<T> {}
+
+/// Rules stating that one node must come before another, such as the `a|b`
+/// page ordering pairs of 2024 day 5. Nodes keep the order in which they were
+/// first seen, which is used to break ties when sorting.
+#[derive(Clone, Debug)]
+pub struct Precedence<T> {
+    nodes: Vec<T>,
+    index: HashMap<T, usize>,
+    successors: Vec<HashSet<usize>>,
+}
+
+impl<T> Default for Precedence<T> {
+    fn default() -> Self {
+        Self {
+            nodes: vec![],
+            index: HashMap::new(),
+            successors: vec![],
+        }
+    }
+}
+
+impl<T> FromStr for Precedence<T>
+where
+    T: FromStr + Hash + Eq + Clone,
+    T::Err: Display,
+{
+    type Err = String;
+
+    /// Parses one `a|b` rule per line, meaning `a` must come before `b`.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut rules = Precedence::new();
+
+        for (number, line) in s.lines().enumerate() {
+            let line = line.trim();
+
+            if line.is_empty() {
+                continue;
+            }
+
+            let (before, after) = line
+                .split_once('|')
+                .ok_or_else(|| format!("line {}: invalid rule '{line}'", number + 1))?;
+
+            let parse = |text: &str| {
+                text.trim()
+                    .parse::<T>()
+                    .map_err(|error| format!("line {}: '{text}': {error}", number + 1))
+            };
+
+            rules.add(parse(before)?, parse(after)?);
+        }
+
+        Ok(rules)
+    }
+}
+
+impl<T> Precedence<T>
+where
+    T: Hash + Eq + Clone,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn intern(&mut self, node: T) -> usize {
+        if let Some(&id) = self.index.get(&node) {
+            return id;
+        }
+
+        let id = self.nodes.len();
+        self.index.insert(node.clone(), id);
+        self.nodes.push(node);
+        self.successors.push(HashSet::new());
+
+        id
+    }
+
+    /// Records that `before` must come before `after`.
+    pub fn add(&mut self, before: T, after: T) {
+        let before = self.intern(before);
+        let after = self.intern(after);
+
+        self.successors[before].insert(after);
+    }
+
+    /// Every node mentioned by a rule, in the order first seen.
+    pub fn nodes(&self) -> &[T] {
+        &self.nodes
+    }
+
+    /// Whether a rule directly states that `before` comes before `after`.
+    pub fn must_precede(&self, before: &T, after: &T) -> bool {
+        match (self.index.get(before), self.index.get(after)) {
+            (Some(&before), Some(&after)) => self.successors[before].contains(&after),
+            _ => false,
+        }
+    }
+
+    /// Orders two nodes by the rule between them, or as equal if there is
+    /// none. This only gives a consistent `sort_by` when every pair in the
+    /// slice is covered by a rule; use `sort_subset` otherwise.
+    pub fn compare(&self, a: &T, b: &T) -> Ordering {
+        if self.must_precede(a, b) {
+            Ordering::Less
+        } else if self.must_precede(b, a) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
+        }
+    }
+
+    /// Whether `sequence` breaks none of the rules between its members.
+    pub fn is_ordered(&self, sequence: &[T]) -> bool {
+        sequence.iter().enumerate().all(|(position, node)| {
+            sequence[..position]
+                .iter()
+                .all(|earlier| !self.must_precede(node, earlier))
+        })
+    }
+
+    /// Orders every node mentioned by a rule, see `sort_subset`.
+    pub fn sort(&self) -> Result<Vec<T>, CycleError<T>> {
+        self.sort_subset(&self.nodes)
+    }
+
+    /// Orders `nodes` so that every rule between two of them holds, using
+    /// Kahn's algorithm. Rules involving other nodes are ignored, as are
+    /// nodes with no rules at all. Whenever the rules allow a choice, the
+    /// ready node that comes earliest in `nodes` is taken first.
+    pub fn sort_subset(&self, nodes: &[T]) -> Result<Vec<T>, CycleError<T>> {
+        let positions: HashMap<usize, usize> = nodes
+            .iter()
+            .enumerate()
+            .filter_map(|(position, node)| Some((*self.index.get(node)?, position)))
+            .collect();
+
+        let mut edges: Vec<Vec<usize>> = vec![vec![]; nodes.len()];
+        let mut in_degree: Vec<usize> = vec![0; nodes.len()];
+
+        for (&id, &position) in &positions {
+            for successor in &self.successors[id] {
+                if let Some(&next) = positions.get(successor) {
+                    edges[position].push(next);
+                    in_degree[next] += 1;
+                }
+            }
+        }
+
+        let mut ready: BinaryHeap<Reverse<usize>> = (0..nodes.len())
+            .filter(|&position| in_degree[position] == 0)
+            .map(Reverse)
+            .collect();
+        let mut sorted: Vec<T> = Vec::with_capacity(nodes.len());
+
+        while let Some(Reverse(position)) = ready.pop() {
+            sorted.push(nodes[position].clone());
+
+            for &next in &edges[position] {
+                in_degree[next] -= 1;
+
+                if in_degree[next] == 0 {
+                    ready.push(Reverse(next));
+                }
+            }
+        }
+
+        if sorted.len() < nodes.len() {
+            return Err(CycleError {
+                cycle: Self::find_cycle(&edges, &in_degree)
+                    .into_iter()
+                    .map(|position| nodes[position].clone())
+                    .collect(),
+            });
+        }
+
+        Ok(sorted)
+    }
+
+    /// Every node Kahn's algorithm left behind still has a predecessor that
+    /// was also left behind, so walking backwards from any of them must
+    /// eventually revisit a node.
+    fn find_cycle(edges: &[Vec<usize>], in_degree: &[usize]) -> Vec<usize> {
+        let mut predecessor: HashMap<usize, usize> = HashMap::new();
+
+        for (position, successors) in edges.iter().enumerate() {
+            if in_degree[position] == 0 {
+                continue;
+            }
+
+            for &next in successors {
+                if in_degree[next] > 0 {
+                    predecessor.entry(next).or_insert(position);
+                }
+            }
+        }
+
+        let Some(mut current) = (0..in_degree.len()).find(|&position| in_degree[position] > 0)
+        else {
+            return vec![];
+        };
+
+        let mut seen: Vec<usize> = vec![];
+
+        while !seen.contains(&current) {
+            seen.push(current);
+            current = predecessor[&current];
+        }
+
+        let start = seen
+            .iter()
+            .position(|&position| position == current)
+            .unwrap();
+        let mut cycle = seen.split_off(start);
+        cycle.reverse();
+        cycle.rotate_right(1);
+
+        cycle
+    }
+}
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let rules: Precedence<u64> = "47|53\n97|13\n\n97|47".parse().unwrap();
+
+        assert_eq!(rules.nodes(), &[47, 53, 97, 13]);
+        assert!(rules.must_precede(&97, &47));
+        assert!(!rules.must_precede(&47, &97));
+        assert!("47-53".parse::<Precedence<u64>>().is_err());
+        assert!("47|x".parse::<Precedence<u64>>().is_err());
+    }
+
+    #[test]
+    fn test_ordering() {
+        let rules: Precedence<char> = "a|b\nb|c\na|c".parse().unwrap();
+
+        assert!(rules.is_ordered(&['a', 'b', 'c']));
+        assert!(rules.is_ordered(&['a', 'x', 'c']));
+        assert!(!rules.is_ordered(&['c', 'a']));
+
+        let mut sequence = vec!['c', 'a', 'b'];
+        sequence.sort_by(|a, b| rules.compare(a, b));
+        assert_eq!(sequence, vec!['a', 'b', 'c']);
+    }
+
+    #[test]
+    fn test_sort() {
+        let rules: Precedence<char> = "d|b\nc|a\nb|a".parse().unwrap();
+
+        assert_eq!(rules.sort(), Ok(vec!['d', 'b', 'c', 'a']));
+        assert_eq!(rules.sort_subset(&['a', 'x', 'b']), Ok(vec!['x', 'b', 'a']));
+    }
+
+    #[test]
+    fn test_cycle() {
+        let rules: Precedence<char> = "a|b\nb|c\nc|d\nd|b".parse().unwrap();
+
+        let error = rules.sort().unwrap_err();
+        assert_eq!(error.cycle, vec!['b', 'c', 'd']);
+
+        assert_eq!(rules.sort_subset(&['a', 'c', 'd']), Ok(vec!['a', 'c', 'd']));
+    }
+}
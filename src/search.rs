@@ -0,0 +1,287 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet},
+    hash::Hash,
+};
+
+use num::Zero;
+
+/// The outcome of a search. Alongside the optimal cost it keeps, for every
+/// state reached, each predecessor through which that state was reached at
+/// its optimal cost. Following these links back from the goals gives every
+/// optimal path, not just one.
+pub struct SearchResult<S, C> {
+    pub cost: C,
+    states: Vec<S>,
+    index: HashMap<S, usize>,
+    predecessors: Vec<Vec<usize>>,
+    goals: Vec<usize>,
+}
+
+impl<S, C> SearchResult<S, C>
+where
+    S: Hash + Eq + Clone,
+{
+    /// Every goal state that was reached at the optimal cost.
+    pub fn goals(&self) -> impl Iterator<Item = &S> {
+        self.goals.iter().map(|&id| &self.states[id])
+    }
+
+    /// The states that lead directly to `state` at its optimal cost.
+    pub fn predecessors(&self, state: &S) -> Vec<&S> {
+        self.index.get(state).map_or(vec![], |&id| {
+            self.predecessors[id]
+                .iter()
+                .map(|&previous| &self.states[previous])
+                .collect()
+        })
+    }
+
+    /// One optimal path, from the start state to the first goal found.
+    pub fn path(&self) -> Vec<S> {
+        let mut path = vec![];
+        let mut current = self.goals[0];
+
+        loop {
+            path.push(self.states[current].clone());
+
+            match self.predecessors[current].first() {
+                Some(&previous) if current != 0 => current = previous,
+                _ => break,
+            }
+        }
+
+        path.reverse();
+        path
+    }
+
+    /// Every state that lies on at least one optimal path to a goal.
+    pub fn optimal_states(&self) -> HashSet<S> {
+        let mut seen: HashSet<usize> = self.goals.iter().copied().collect();
+        let mut stack = self.goals.clone();
+
+        while let Some(id) = stack.pop() {
+            for &previous in &self.predecessors[id] {
+                if seen.insert(previous) {
+                    stack.push(previous);
+                }
+            }
+        }
+
+        seen.into_iter().map(|id| self.states[id].clone()).collect()
+    }
+}
+
+/// Finds the cheapest way from `start` to any state for which `is_goal`
+/// returns true. `successors` lists the states reachable from a state along
+/// with the cost of each step. Costs must not be negative.
+pub fn dijkstra<S, C, FN, IN, FG>(
+    start: S,
+    successors: FN,
+    is_goal: FG,
+) -> Option<SearchResult<S, C>>
+where
+    S: Hash + Eq + Clone,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FG: FnMut(&S) -> bool,
+{
+    astar(start, successors, |_| C::zero(), is_goal)
+}
+
+/// Like `dijkstra`, but guided by `heuristic`, an estimate of the remaining
+/// cost from a state. The heuristic must never overestimate, and must be
+/// consistent for `optimal_states` to list every optimal path.
+pub fn astar<S, C, FN, IN, FH, FG>(
+    start: S,
+    mut successors: FN,
+    mut heuristic: FH,
+    mut is_goal: FG,
+) -> Option<SearchResult<S, C>>
+where
+    S: Hash + Eq + Clone,
+    C: Zero + Ord + Copy,
+    FN: FnMut(&S) -> IN,
+    IN: IntoIterator<Item = (S, C)>,
+    FH: FnMut(&S) -> C,
+    FG: FnMut(&S) -> bool,
+{
+    let mut states: Vec<S> = vec![start.clone()];
+    let mut index: HashMap<S, usize> = HashMap::from([(start.clone(), 0)]);
+    let mut cost_so_far: Vec<C> = vec![C::zero()];
+    let mut predecessors: Vec<Vec<usize>> = vec![vec![]];
+    let mut settled: Vec<bool> = vec![false];
+
+    let mut frontier: BinaryHeap<Reverse<(C, usize)>> = BinaryHeap::new();
+    frontier.push(Reverse((heuristic(&start), 0)));
+
+    let mut best: Option<C> = None;
+    let mut goals: Vec<usize> = vec![];
+
+    while let Some(Reverse((estimate, id))) = frontier.pop() {
+        if best.is_some_and(|best| estimate > best) {
+            break;
+        }
+
+        if settled[id] {
+            continue;
+        }
+
+        settled[id] = true;
+        let cost = cost_so_far[id];
+
+        if is_goal(&states[id]) {
+            best = Some(cost);
+            goals.push(id);
+            continue;
+        }
+
+        for (next, step) in successors(&states[id]) {
+            let new_cost = cost + step;
+
+            let next_id = match index.get(&next) {
+                Some(&next_id) => next_id,
+                None => {
+                    let next_id = states.len();
+                    index.insert(next.clone(), next_id);
+                    states.push(next);
+                    cost_so_far.push(new_cost);
+                    predecessors.push(vec![]);
+                    settled.push(false);
+
+                    let estimate = new_cost + heuristic(&states[next_id]);
+                    frontier.push(Reverse((estimate, next_id)));
+                    predecessors[next_id].push(id);
+                    continue;
+                }
+            };
+
+            // A tie is another optimal way in, even if `next` has already
+            // been settled, for example through a zero-cost step or when it
+            // was popped first on an equal estimate.
+            if new_cost == cost_so_far[next_id] {
+                predecessors[next_id].push(id);
+            } else if new_cost < cost_so_far[next_id] && !settled[next_id] {
+                cost_so_far[next_id] = new_cost;
+                predecessors[next_id] = vec![id];
+
+                let estimate = new_cost + heuristic(&states[next_id]);
+                frontier.push(Reverse((estimate, next_id)));
+            }
+        }
+    }
+
+    Some(SearchResult {
+        cost: best?,
+        states,
+        index,
+        predecessors,
+        goals,
+    })
+}
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    /// A diamond with two equally cheap routes from `a` to `d`, plus a
+    /// dearer direct edge.
+    fn edges(state: &char) -> Vec<(char, u32)> {
+        match state {
+            'a' => vec![('b', 1), ('c', 2), ('d', 5)],
+            'b' => vec![('d', 2)],
+            'c' => vec![('d', 1)],
+            'd' => vec![('e', 1)],
+            _ => vec![],
+        }
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let result = dijkstra('a', edges, |state| *state == 'd').unwrap();
+
+        assert_eq!(result.cost, 3);
+        assert_eq!(result.path().first(), Some(&'a'));
+        assert_eq!(result.path().last(), Some(&'d'));
+        assert_eq!(result.path().len(), 3);
+        assert_eq!(result.predecessors(&'d').len(), 2);
+
+        let mut optimal: Vec<char> = result.optimal_states().into_iter().collect();
+        optimal.sort();
+        assert_eq!(optimal, vec!['a', 'b', 'c', 'd']);
+
+        assert!(dijkstra('a', edges, |state| *state == 'z').is_none());
+    }
+
+    fn sorted(result: &SearchResult<char, u32>) -> Vec<char> {
+        let mut optimal: Vec<char> = result.optimal_states().into_iter().collect();
+        optimal.sort();
+        optimal
+    }
+
+    #[test]
+    fn test_astar_tied_estimates() {
+        let result = astar(
+            's',
+            |state| match state {
+                's' => vec![('a', 1)],
+                'a' => vec![('g', 2), ('b', 1)],
+                'b' => vec![('g', 1)],
+                _ => vec![],
+            },
+            |state| match state {
+                's' => 3,
+                'a' => 2,
+                'b' => 1,
+                _ => 0,
+            },
+            |state| *state == 'g',
+        )
+        .unwrap();
+
+        assert_eq!(result.cost, 3);
+        assert_eq!(result.predecessors(&'g').len(), 2);
+        assert_eq!(sorted(&result), vec!['a', 'b', 'g', 's']);
+    }
+
+    #[test]
+    fn test_dijkstra_zero_cost_step() {
+        let result = dijkstra(
+            's',
+            |state| match state {
+                's' => vec![('g', 1), ('a', 1)],
+                'a' => vec![('g', 0)],
+                _ => vec![],
+            },
+            |state| *state == 'g',
+        )
+        .unwrap();
+
+        assert_eq!(result.cost, 1);
+        assert_eq!(sorted(&result), vec!['a', 'g', 's']);
+        assert_eq!(result.path(), vec!['s', 'g']);
+    }
+
+    #[test]
+    fn test_astar() {
+        let goal = (5i32, 3i32);
+        let result = astar(
+            (0i32, 0i32),
+            |&(x, y)| {
+                [(x + 1, y), (x - 1, y), (x, y + 1), (x, y - 1)]
+                    .into_iter()
+                    .filter(|&(x, y)| (0..=6).contains(&x) && (0..=6).contains(&y))
+                    .map(|next| (next, 1))
+                    .collect::<Vec<_>>()
+            },
+            |&(x, y)| (goal.0 - x).abs() + (goal.1 - y).abs(),
+            |state| *state == goal,
+        )
+        .unwrap();
+
+        assert_eq!(result.cost, 8);
+        assert_eq!(result.path().len(), 9);
+        assert_eq!(result.optimal_states().len(), 24);
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::VecDeque;
+
+/// Something that advances in discrete ticks.
+pub trait Simulation {
+    /// A snapshot of the simulation, kept in the history for replay.
+    type Frame;
+
+    fn step(&mut self);
+
+    fn frame(&self) -> Self::Frame;
+}
+
+/// The most recent frames of a run, oldest first. Once `capacity` frames have
+/// been recorded the oldest is dropped for each new one.
+pub struct History<F> {
+    capacity: usize,
+    frames: VecDeque<(usize, F)>,
+}
+
+impl<F> History<F> {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            frames: VecDeque::with_capacity(capacity),
+        }
+    }
+
+    pub fn push(&mut self, tick: usize, frame: F) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.frames.len() == self.capacity {
+            self.frames.pop_front();
+        }
+
+        self.frames.push_back((tick, frame));
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// The frame recorded at `tick`, if it is still held.
+    pub fn get(&self, tick: usize) -> Option<&F> {
+        self.frames
+            .iter()
+            .find(|(recorded, _)| *recorded == tick)
+            .map(|(_, frame)| frame)
+    }
+
+    /// A cursor over the recorded frames, starting at the oldest.
+    pub fn replay(&self) -> Replay<'_, F> {
+        Replay {
+            history: self,
+            position: 0,
+        }
+    }
+}
+
+/// Steps back and forth through a `History`.
+pub struct Replay<'a, F> {
+    history: &'a History<F>,
+    position: usize,
+}
+
+impl<'a, F> Replay<'a, F> {
+    /// The tick and frame under the cursor.
+    pub fn current(&self) -> Option<(usize, &'a F)> {
+        self.history
+            .frames
+            .get(self.position)
+            .map(|(tick, frame)| (*tick, frame))
+    }
+
+    /// Moves the cursor one frame later, returning the new frame.
+    pub fn forward(&mut self) -> Option<(usize, &'a F)> {
+        if self.position + 1 >= self.history.len() {
+            return None;
+        }
+
+        self.position += 1;
+        self.current()
+    }
+
+    /// Moves the cursor one frame earlier, returning the new frame.
+    pub fn backward(&mut self) -> Option<(usize, &'a F)> {
+        if self.position == 0 {
+            return None;
+        }
+
+        self.position -= 1;
+        self.current()
+    }
+
+    /// Moves the cursor to the latest frame.
+    pub fn to_end(&mut self) -> Option<(usize, &'a F)> {
+        self.position = self.history.len().saturating_sub(1);
+        self.current()
+    }
+}
+
+type FrameHook<'a, S> = Box<dyn FnMut(usize, &S) + 'a>;
+
+/// Drives a `Simulation`, counting ticks, calling frame hooks after every
+/// step and optionally recording a bounded history.
+pub struct Runner<'a, S: Simulation> {
+    simulation: &'a mut S,
+    tick: usize,
+    history: Option<History<S::Frame>>,
+    hooks: Vec<FrameHook<'a, S>>,
+}
+
+impl<'a, S: Simulation> Runner<'a, S> {
+    pub fn new(simulation: &'a mut S) -> Self {
+        Self {
+            simulation,
+            tick: 0,
+            history: None,
+            hooks: vec![],
+        }
+    }
+
+    /// Records the last `capacity` frames, starting with the current one.
+    pub fn with_history(mut self, capacity: usize) -> Self {
+        let mut history = History::new(capacity);
+        history.push(self.tick, self.simulation.frame());
+
+        self.history = Some(history);
+        self
+    }
+
+    /// Calls `hook` with the tick number after every step, e.g. to print or
+    /// render each frame.
+    pub fn on_frame<F>(mut self, hook: F) -> Self
+    where
+        F: FnMut(usize, &S) + 'a,
+    {
+        self.hooks.push(Box::new(hook));
+        self
+    }
+
+    pub fn tick(&self) -> usize {
+        self.tick
+    }
+
+    pub fn simulation(&self) -> &S {
+        self.simulation
+    }
+
+    pub fn history(&self) -> Option<&History<S::Frame>> {
+        self.history.as_ref()
+    }
+
+    pub fn step(&mut self) {
+        self.simulation.step();
+        self.tick += 1;
+
+        if let Some(history) = self.history.as_mut() {
+            history.push(self.tick, self.simulation.frame());
+        }
+
+        for hook in self.hooks.iter_mut() {
+            hook(self.tick, self.simulation);
+        }
+    }
+
+    pub fn run(&mut self, steps: usize) {
+        for _ in 0..steps {
+            self.step();
+        }
+    }
+
+    /// Steps until `predicate` holds, checking before each step, and returns
+    /// the tick at which it did. Gives up after `limit` steps.
+    pub fn run_until<P>(&mut self, mut predicate: P, limit: usize) -> Option<usize>
+    where
+        P: FnMut(&S) -> bool,
+    {
+        for _ in 0..limit {
+            if predicate(self.simulation) {
+                return Some(self.tick);
+            }
+
+            self.step();
+        }
+
+        predicate(self.simulation).then_some(self.tick)
+    }
+}
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    struct Counter {
+        value: u32,
+    }
+
+    impl Simulation for Counter {
+        type Frame = u32;
+
+        fn step(&mut self) {
+            self.value = self.value * 2 + 1;
+        }
+
+        fn frame(&self) -> u32 {
+            self.value
+        }
+    }
+
+    #[test]
+    fn test_run_until() {
+        let mut counter = Counter { value: 0 };
+        let mut seen: Vec<usize> = vec![];
+
+        let mut runner = Runner::new(&mut counter).on_frame(|tick, _| seen.push(tick));
+        assert_eq!(runner.run_until(|counter| counter.value > 20, 10), Some(5));
+        assert_eq!(runner.run_until(|counter| counter.value == 0, 3), None);
+        assert_eq!(runner.tick(), 8);
+
+        drop(runner);
+        assert_eq!(seen, (1..=8).collect::<Vec<_>>());
+        assert_eq!(counter.value, 255);
+    }
+
+    #[test]
+    fn test_history_replay() {
+        let mut counter = Counter { value: 0 };
+        let mut runner = Runner::new(&mut counter).with_history(3);
+        runner.run(5);
+
+        let history = runner.history().unwrap();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history.get(1), None);
+        assert_eq!(history.get(4), Some(&15));
+
+        let mut replay = history.replay();
+        assert_eq!(replay.current(), Some((3, &7)));
+        assert_eq!(replay.backward(), None);
+        assert_eq!(replay.forward(), Some((4, &15)));
+        assert_eq!(replay.to_end(), Some((5, &31)));
+        assert_eq!(replay.forward(), None);
+        assert_eq!(replay.backward(), Some((4, &15)));
+    }
+}
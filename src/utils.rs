@@ -0,0 +1,34 @@
+use regex::Regex;
+
+mod columns;
+mod ints;
+mod scan;
+mod sections;
+mod tokens;
+
+pub use columns::{columns, columns_by};
+pub use ints::{ints, ints_n, uints};
+pub use scan::{ScanCaptures, ScanError, ScanPattern};
+pub use sections::{
+    parse_records, parse_sections, records, sections, Records, Section, SectionError,
+    SectionParsers,
+};
+pub use tokens::{TokenMatch, TokenMode, TokenScanner};
+
+pub fn split_keep<'a>(r: &Regex, text: &'a str) -> Vec<&'a str> {
+    let mut result = Vec::new();
+    let mut last = 0;
+    for m in r.find_iter(text) {
+        if last != m.start() {
+            result.push(&text[last..m.start()]);
+        }
+        result.push(m.as_str());
+        last = m.start() + m.len();
+    }
+
+    if last < text.len() {
+        result.push(&text[last..]);
+    }
+
+    result
+}
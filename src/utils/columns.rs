@@ -0,0 +1,87 @@
+use std::{fmt::Display, str::FromStr};
+
+fn parse_columns<'a, T, const N: usize, S, I>(
+    input: &'a str,
+    split: S,
+) -> Result<[Vec<T>; N], String>
+where
+    T: FromStr,
+    T::Err: Display,
+    S: Fn(&'a str) -> I,
+    I: Iterator<Item = &'a str>,
+{
+    let mut columns: [Vec<T>; N] = std::array::from_fn(|_| Vec::new());
+
+    for (index, line) in input.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let fields: Vec<&str> = split(line).collect();
+
+        if fields.len() != N {
+            return Err(format!(
+                "line {}: expected {N} columns but found {}",
+                index + 1,
+                fields.len()
+            ));
+        }
+
+        for (column, field) in columns.iter_mut().zip(fields) {
+            let value = field.trim().parse::<T>().map_err(|error| {
+                format!("line {}: failed to parse '{field}': {error}", index + 1)
+            })?;
+
+            column.push(value);
+        }
+    }
+
+    Ok(columns)
+}
+
+/// Parses whitespace-separated rows into `N` columns, e.g.
+/// `let [left, right] = columns::<u64, 2>(input)?`. Every non-blank line must
+/// have exactly `N` fields.
+pub fn columns<T, const N: usize>(input: &str) -> Result<[Vec<T>; N], String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    parse_columns(input, str::split_whitespace)
+}
+
+/// Like `columns`, but splits each line on `delimiter` and trims the fields.
+pub fn columns_by<T, const N: usize>(input: &str, delimiter: &str) -> Result<[Vec<T>; N], String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    parse_columns(input, |line| line.split(delimiter))
+}
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    #[test]
+    fn test_columns() {
+        let [left, right] = columns::<u64, 2>("3   4\n4   3\n2 5\n\n").unwrap();
+
+        assert_eq!(left, vec![3, 4, 2]);
+        assert_eq!(right, vec![4, 3, 5]);
+
+        let [a, b, c] = columns_by::<i32, 3>("1, 2, 3\n-4,5 ,6", ",").unwrap();
+        assert_eq!((a, b, c), (vec![1, -4], vec![2, 5], vec![3, 6]));
+    }
+
+    #[test]
+    fn test_columns_errors() {
+        assert_eq!(
+            columns::<u64, 2>("1 2\n3 4 5"),
+            Err("line 2: expected 2 columns but found 3".to_string())
+        );
+        assert!(columns::<u64, 2>("1 x")
+            .unwrap_err()
+            .starts_with("line 1: failed to parse 'x'"));
+    }
+}
@@ -0,0 +1,156 @@
+use std::{fmt::Display, str::FromStr};
+
+/// Iterator over the integer-looking substrings of a line, created by
+/// `ints` and `uints`.
+struct IntTokens<'a> {
+    bytes: &'a [u8],
+    text: &'a str,
+    position: usize,
+    signed: bool,
+}
+
+impl<'a> Iterator for IntTokens<'a> {
+    type Item = &'a str;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.position < self.bytes.len() && !self.bytes[self.position].is_ascii_digit() {
+            self.position += 1;
+        }
+
+        if self.position == self.bytes.len() {
+            return None;
+        }
+
+        let mut start = self.position;
+        if self.signed && start > 0 && self.bytes[start - 1] == b'-' {
+            start -= 1;
+        }
+
+        while self.position < self.bytes.len() && self.bytes[self.position].is_ascii_digit() {
+            self.position += 1;
+        }
+
+        Some(&self.text[start..self.position])
+    }
+}
+
+fn int_tokens(line: &str, signed: bool) -> IntTokens<'_> {
+    IntTokens {
+        bytes: line.as_bytes(),
+        text: line,
+        position: 0,
+        signed,
+    }
+}
+
+fn parse_token<T>(token: &str) -> Result<T, String>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    token
+        .parse::<T>()
+        .map_err(|error| format!("Failed to parse '{token}': {error}"))
+}
+
+/// Every integer in `line`, where a `-` directly before a number makes it
+/// negative. Anything that is not part of a number is skipped, so
+/// `p=0,4 v=3,-3` yields `0, 4, 3, -3`.
+///
+/// Numbers that do not fit in `T`, including negative numbers for an
+/// unsigned `T`, yield an error in their place.
+pub fn ints<T>(line: &str) -> impl Iterator<Item = Result<T, String>> + '_
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    int_tokens(line, true).map(|token| parse_token(token))
+}
+
+/// Every run of digits in `line`, ignoring any signs, so `1-3` yields `1, 3`.
+///
+/// Numbers that do not fit in `T` yield an error in their place.
+pub fn uints<T>(line: &str) -> impl Iterator<Item = Result<T, String>> + '_
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    int_tokens(line, false).map(|token| parse_token(token))
+}
+
+/// Exactly `N` signed integers from `line`, for destructuring fixed-format
+/// lines such as `let [x, y, dx, dy] = ints_n::<i32, 4>(line)?`.
+pub fn ints_n<T, const N: usize>(line: &str) -> Result<[T; N], String>
+where
+    T: FromStr + Default + Copy,
+    T::Err: Display,
+{
+    let mut values = [T::default(); N];
+    let mut count = 0;
+
+    for token in int_tokens(line, true) {
+        if count < N {
+            values[count] = parse_token(token)?;
+        }
+
+        count += 1;
+    }
+
+    if count != N {
+        return Err(format!(
+            "Expected {N} integers but found {count} in '{line}'"
+        ));
+    }
+
+    Ok(values)
+}
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    #[test]
+    fn test_ints() {
+        let values: Result<Vec<i32>, String> = ints("p=0,4 v=3,-3").collect();
+        assert_eq!(values, Ok(vec![0, 4, 3, -3]));
+
+        let values: Result<Vec<i64>, String> = ints("Button A: X+94, Y+34").collect();
+        assert_eq!(values, Ok(vec![94, 34]));
+
+        assert_eq!(ints::<i32>("no numbers - here").count(), 0);
+    }
+
+    #[test]
+    fn test_uints() {
+        let values: Result<Vec<u32>, String> = uints("1-3 a: abcde").collect();
+        assert_eq!(values, Ok(vec![1, 3]));
+
+        let values: Result<Vec<u32>, String> = uints("x=-5").collect();
+        assert_eq!(values, Ok(vec![5]));
+    }
+
+    #[test]
+    fn test_unparsable() {
+        let values: Vec<Result<u8, String>> = ints("1 300 -5 2").collect();
+
+        assert_eq!(values.len(), 4);
+        assert_eq!(values[0], Ok(1));
+        assert!(values[1]
+            .as_ref()
+            .is_err_and(|error| error.contains("'300'")));
+        assert!(values[2]
+            .as_ref()
+            .is_err_and(|error| error.contains("'-5'")));
+        assert_eq!(values[3], Ok(2));
+
+        assert!(uints::<u32>("99999999999").next().unwrap().is_err());
+    }
+
+    #[test]
+    fn test_ints_n() {
+        assert_eq!(ints_n::<i32, 4>("p=2,4 v=2,-3"), Ok([2, 4, 2, -3]));
+        assert!(ints_n::<i32, 3>("p=2,4 v=2,-3").is_err());
+        assert!(ints_n::<i32, 5>("p=2,4 v=2,-3").is_err());
+        assert!(ints_n::<u8, 2>("300, 1").is_err());
+    }
+}
@@ -0,0 +1,232 @@
+use std::{any::type_name, fmt::Display, str::FromStr};
+
+use regex::{Captures, Regex};
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct ScanError {
+    pub pattern: String,
+    pub input: String,
+    pub message: String,
+}
+
+impl Display for ScanError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Failed to scan '{}' with '{}': {}",
+            self.input, self.pattern, self.message
+        )
+    }
+}
+
+impl std::error::Error for ScanError {}
+
+/// A compiled `scan!` pattern. Usually created once per call site by the
+/// macros rather than by hand.
+pub struct ScanPattern {
+    source: String,
+    regex: Regex,
+}
+
+impl ScanPattern {
+    /// A pattern where each `{}` captures some text and everything else must
+    /// match literally. The whole input has to match.
+    pub fn format(pattern: &str) -> Self {
+        let literals: Vec<String> = pattern.split("{}").map(regex::escape).collect();
+
+        Self {
+            source: pattern.to_string(),
+            regex: Regex::new(&format!("^{}$", literals.join("(.+?)"))).unwrap(),
+        }
+    }
+
+    /// A regular expression whose capture groups are parsed in order. It may
+    /// match anywhere in the input.
+    pub fn regex(pattern: &str) -> Self {
+        Self {
+            source: pattern.to_string(),
+            regex: Regex::new(pattern)
+                .unwrap_or_else(|error| panic!("Invalid scan pattern '{pattern}': {error}")),
+        }
+    }
+
+    fn error(&self, input: &str, message: String) -> ScanError {
+        ScanError {
+            pattern: self.source.clone(),
+            input: input.to_string(),
+            message,
+        }
+    }
+
+    pub fn captures<'p, 't>(&'p self, input: &'t str) -> Result<ScanCaptures<'p, 't>, ScanError> {
+        match self.regex.captures(input) {
+            Some(captures) => Ok(ScanCaptures {
+                pattern: self,
+                input,
+                captures,
+                group: 1,
+            }),
+            None => Err(self.error(input, "input does not match".to_string())),
+        }
+    }
+
+    pub fn captures_iter<'p, 't>(
+        &'p self,
+        input: &'t str,
+    ) -> impl Iterator<Item = ScanCaptures<'p, 't>> {
+        self.regex
+            .captures_iter(input)
+            .map(move |captures| ScanCaptures {
+                pattern: self,
+                input,
+                captures,
+                group: 1,
+            })
+    }
+}
+
+/// The capture groups of one match, handed out in order by `parse_next`.
+pub struct ScanCaptures<'p, 't> {
+    pattern: &'p ScanPattern,
+    input: &'t str,
+    captures: Captures<'t>,
+    group: usize,
+}
+
+impl<'p, 't> ScanCaptures<'p, 't> {
+    pub fn parse_next<T>(&mut self) -> Result<T, ScanError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let group = self.group;
+        self.group += 1;
+
+        if group >= self.captures.len() {
+            return Err(self.pattern.error(
+                self.input,
+                format!(
+                    "pattern only has {} groups but more were requested",
+                    self.captures.len() - 1
+                ),
+            ));
+        }
+
+        let text = self
+            .captures
+            .get(group)
+            .ok_or_else(|| {
+                self.pattern
+                    .error(self.input, format!("group {group} did not match"))
+            })?
+            .as_str();
+
+        text.parse::<T>().map_err(|error| {
+            self.pattern.error(
+                self.input,
+                format!(
+                    "group {group} ('{text}') is not a valid {}: {error}",
+                    type_name::<T>()
+                ),
+            )
+        })
+    }
+}
+
+/// Matches `input` against a pattern and parses the captures into a tuple.
+/// The pattern is compiled once per call site.
+///
+/// ```ignore
+/// let (button, x, y) = scan!("Button {}: X+{}, Y+{}", line => char, i64, i64)?;
+/// let (a, b) = scan!(regex r"mul\((\d+),(\d+)\)", line => u64, u64)?;
+/// ```
+#[macro_export]
+macro_rules! scan {
+    (regex $pattern:expr, $input:expr => $($ty:ty),+ $(,)?) => {{
+        static PATTERN: std::sync::OnceLock<$crate::utils::ScanPattern> =
+            std::sync::OnceLock::new();
+
+        PATTERN
+            .get_or_init(|| $crate::utils::ScanPattern::regex($pattern))
+            .captures($input)
+            .and_then(|mut captures| Ok(($(captures.parse_next::<$ty>()?,)+)))
+    }};
+    ($pattern:expr, $input:expr => $($ty:ty),+ $(,)?) => {{
+        static PATTERN: std::sync::OnceLock<$crate::utils::ScanPattern> =
+            std::sync::OnceLock::new();
+
+        PATTERN
+            .get_or_init(|| $crate::utils::ScanPattern::format($pattern))
+            .captures($input)
+            .and_then(|mut captures| Ok(($(captures.parse_next::<$ty>()?,)+)))
+    }};
+}
+
+/// Like `scan!`, but yields a parsed tuple for every non-overlapping match in
+/// `input`.
+#[macro_export]
+macro_rules! scan_all {
+    (regex $pattern:expr, $input:expr => $($ty:ty),+ $(,)?) => {{
+        static PATTERN: std::sync::OnceLock<$crate::utils::ScanPattern> =
+            std::sync::OnceLock::new();
+
+        PATTERN
+            .get_or_init(|| $crate::utils::ScanPattern::regex($pattern))
+            .captures_iter($input)
+            .map(|mut captures| -> Result<_, $crate::utils::ScanError> {
+                Ok(($(captures.parse_next::<$ty>()?,)+))
+            })
+    }};
+    ($pattern:expr, $input:expr => $($ty:ty),+ $(,)?) => {{
+        static PATTERN: std::sync::OnceLock<$crate::utils::ScanPattern> =
+            std::sync::OnceLock::new();
+
+        PATTERN
+            .get_or_init(|| $crate::utils::ScanPattern::format($pattern))
+            .captures_iter($input)
+            .map(|mut captures| -> Result<_, $crate::utils::ScanError> {
+                Ok(($(captures.parse_next::<$ty>()?,)+))
+            })
+    }};
+}
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+    use crate::{scan, scan_all};
+
+    #[test]
+    fn test_scan_format() {
+        let parsed = scan!("Button {}: X+{}, Y+{}", "Button A: X+94, Y+34" => char, i64, i64);
+        assert_eq!(parsed, Ok(('A', 94, 34)));
+
+        let parsed = scan!("p={},{} v={},{}", "p=0,4 v=3,-3" => i32, i32, i32, i32);
+        assert_eq!(parsed, Ok((0, 4, 3, -3)));
+    }
+
+    #[test]
+    fn test_scan_regex() {
+        let parsed = scan!(regex r"Game (\d+)", "Game 12: 3 blue" => u64);
+        assert_eq!(parsed, Ok((12,)));
+
+        let products: Vec<u64> = scan_all!(
+            regex r"mul\(([0-9]{1,3}),([0-9]{1,3})\)",
+            "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)" => u64, u64
+        )
+        .map(|captures| captures.map(|(a, b)| a * b).unwrap())
+        .collect();
+        assert_eq!(products, vec![8, 25]);
+    }
+
+    #[test]
+    fn test_scan_errors() {
+        let error = scan!("{} -> {}", "a to b" => String, String).unwrap_err();
+        assert_eq!(error.message, "input does not match");
+
+        let error = scan!("{},{}", "1,x" => u8, u8).unwrap_err();
+        assert!(error.message.starts_with("group 2 ('x') is not a valid u8"));
+
+        let error = scan!(regex r"(\d+)", "42" => u8, u8).unwrap_err();
+        assert!(error.message.starts_with("pattern only has 1 groups"));
+    }
+}
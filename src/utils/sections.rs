@@ -0,0 +1,375 @@
+use std::{borrow::Cow, fmt::Display, str::FromStr};
+
+/// A block of consecutive non-blank lines from a puzzle input.
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct Section<'a> {
+    /// 1-based position of this section within the input.
+    pub number: usize,
+    /// 1-based line number of the first line of this section.
+    pub first_line: usize,
+    /// The section exactly as it appears in the input, so lines may still
+    /// end in `\r`. Use `lines` or `normalised` to get rid of it.
+    pub text: &'a str,
+}
+
+#[derive(PartialEq, Eq, Debug)]
+pub struct SectionError {
+    pub section: usize,
+    pub line: usize,
+    pub message: String,
+}
+
+impl Display for SectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "section {}, line {}: {}",
+            self.section, self.line, self.message
+        )
+    }
+}
+
+impl std::error::Error for SectionError {}
+
+impl<'a> Section<'a> {
+    pub fn error(&self, line: usize, message: impl Display) -> SectionError {
+        SectionError {
+            section: self.number,
+            line,
+            message: message.to_string(),
+        }
+    }
+
+    /// The lines of this section, with trailing whitespace (including `\r`)
+    /// removed.
+    pub fn lines(&self) -> impl Iterator<Item = &'a str> {
+        self.text.lines().map(str::trim_end)
+    }
+
+    /// The lines of this section joined with `\n`. Only copied from the input
+    /// when trimming the lines changes anything.
+    pub fn normalised(&self) -> Cow<'a, str> {
+        if self.text.contains('\r')
+            || self
+                .text
+                .lines()
+                .any(|line| line.ends_with(char::is_whitespace))
+        {
+            Cow::Owned(self.lines().collect::<Vec<_>>().join("\n"))
+        } else {
+            Cow::Borrowed(self.text)
+        }
+    }
+
+    /// Parses the whole section at once, as it appears in the input. Errors
+    /// are reported against the first line of the section.
+    pub fn parse<T, E, F>(&self, parse_fn: F) -> Result<T, SectionError>
+    where
+        E: Display,
+        F: FnOnce(&'a str) -> Result<T, E>,
+    {
+        parse_fn(self.text).map_err(|error| self.error(self.first_line, error))
+    }
+
+    /// Parses each line of the section separately, so errors point at the
+    /// line that failed.
+    pub fn parse_lines<T, E, F>(&self, mut parse_fn: F) -> Result<Vec<T>, SectionError>
+    where
+        E: Display,
+        F: FnMut(&'a str) -> Result<T, E>,
+    {
+        self.lines()
+            .enumerate()
+            .map(|(offset, line)| {
+                parse_fn(line).map_err(|error| self.error(self.first_line + offset, error))
+            })
+            .collect()
+    }
+}
+
+/// Iterator over the blank-line separated blocks of an input, created by
+/// `records`.
+pub struct Records<'a> {
+    input: &'a str,
+    lines: std::iter::Enumerate<std::str::SplitInclusive<'a, char>>,
+    offset: usize,
+    count: usize,
+}
+
+impl<'a> Iterator for Records<'a> {
+    type Item = Section<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut start: Option<(usize, usize)> = None;
+        let mut end = 0;
+
+        for (index, line) in self.lines.by_ref() {
+            let line_offset = self.offset;
+            self.offset += line.len();
+
+            if line.trim().is_empty() {
+                if start.is_some() {
+                    break;
+                }
+            } else {
+                if start.is_none() {
+                    start = Some((index + 1, line_offset));
+                }
+
+                end = line_offset + line.trim_end().len();
+            }
+        }
+
+        let (first_line, start_offset) = start?;
+        self.count += 1;
+
+        Some(Section {
+            number: self.count,
+            first_line,
+            text: &self.input[start_offset..end],
+        })
+    }
+}
+
+/// Iterates over the blocks of `input` separated by one or more blank lines.
+/// Lines containing only whitespace count as blank, and trailing whitespace
+/// (including `\r`) is trimmed from the end of each block.
+pub fn records(input: &str) -> Records<'_> {
+    Records {
+        input,
+        lines: input.split_inclusive('\n').enumerate(),
+        offset: 0,
+        count: 0,
+    }
+}
+
+/// Parses every block of `input` as a `T`, reporting errors against the
+/// first line of the block that failed. Each block is normalised first, so
+/// `T` never sees a `\r`.
+pub fn parse_records<T>(input: &str) -> Result<Vec<T>, SectionError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    records(input)
+        .map(|record| {
+            record
+                .normalised()
+                .parse::<T>()
+                .map_err(|error| record.error(record.first_line, error))
+        })
+        .collect()
+}
+
+/// Splits `input` into sections separated by one or more blank lines.
+pub fn sections(input: &str) -> Vec<Section<'_>> {
+    records(input).collect()
+}
+
+/// A tuple of per-section parsers, one for each section expected in the
+/// input.
+pub trait SectionParsers<'a> {
+    type Output;
+
+    fn parse_sections(self, sections: &[Section<'a>]) -> Result<Self::Output, SectionError>;
+}
+
+macro_rules! impl_section_parsers {
+    ($count:literal; $($parser:ident $output:ident $index:tt),+) => {
+        impl<'a, $($parser, $output),+> SectionParsers<'a> for ($($parser,)+)
+        where
+            $($parser: FnOnce(Section<'a>) -> Result<$output, SectionError>),+
+        {
+            type Output = ($($output,)+);
+
+            fn parse_sections(
+                self,
+                sections: &[Section<'a>],
+            ) -> Result<Self::Output, SectionError> {
+                if sections.len() != $count {
+                    let line = sections.last().map_or(1, |section| section.first_line);
+
+                    return Err(SectionError {
+                        section: sections.len(),
+                        line,
+                        message: format!(
+                            "expected {} sections, found {}",
+                            $count,
+                            sections.len()
+                        ),
+                    });
+                }
+
+                Ok(($((self.$index)(sections[$index])?,)+))
+            }
+        }
+    };
+}
+
+impl_section_parsers!(1; A TA 0);
+impl_section_parsers!(2; A TA 0, B TB 1);
+impl_section_parsers!(3; A TA 0, B TB 1, C TC 2);
+impl_section_parsers!(4; A TA 0, B TB 1, C TC 2, D TD 3);
+impl_section_parsers!(5; A TA 0, B TB 1, C TC 2, D TD 3, E TE 4);
+
+/// Splits `input` on blank lines and runs each parser in `parsers` on the
+/// matching section, e.g. a grid followed by a list of moves. Closures should
+/// return owned values; use a `fn` to return slices borrowed from `input`.
+pub fn parse_sections<'a, P>(input: &'a str, parsers: P) -> Result<P::Output, SectionError>
+where
+    P: SectionParsers<'a>,
+{
+    parsers.parse_sections(&sections(input))
+}
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    const EXAMPLE_INPUT: &str = "47|53
+97|13
+
+75,47,61
+97,61,53,29,13
+
+
+1";
+
+    #[derive(PartialEq, Debug)]
+    struct Totals(u64);
+
+    impl FromStr for Totals {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            s.lines()
+                .map(|line| line.parse::<u64>().map_err(|e| e.to_string()))
+                .sum::<Result<u64, String>>()
+                .map(Totals)
+        }
+    }
+
+    #[test]
+    fn test_sections() {
+        let sections = sections(EXAMPLE_INPUT);
+
+        assert_eq!(sections.len(), 3);
+        assert_eq!(sections[0].text, "47|53\n97|13");
+        assert_eq!(sections[1].first_line, 4);
+        assert_eq!(sections[2].first_line, 8);
+        assert_eq!(sections[2].number, 3);
+    }
+
+    #[test]
+    fn test_crlf() {
+        let sections = sections("a\r\nb\r\n\r\nc\r\n");
+
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].lines().collect::<Vec<_>>(), vec!["a", "b"]);
+        assert_eq!(sections[1].text, "c");
+    }
+
+    fn split_rules(section: Section<'_>) -> Result<Vec<(&str, &str)>, SectionError> {
+        section.parse_lines(|line| line.split_once('|').ok_or("missing '|'"))
+    }
+
+    #[test]
+    fn test_parse_normalised() {
+        let input = "47|53  \r\n97|13\r\n\r\n1\t\r\n2\r\n";
+
+        let (rules, total) = parse_sections(
+            input,
+            (split_rules, |section: Section| {
+                section.parse(|text| Ok::<_, String>(text.len()))
+            }),
+        )
+        .unwrap();
+
+        assert_eq!(rules, vec![("47", "53"), ("97", "13")]);
+        // `parse` sees the section as it is in the input: "1\t\r\n2".
+        assert_eq!(total, 5);
+
+        let totals: Vec<Totals> = parse_records("1\t\r\n2\r\n\r\n3 \r\n").unwrap();
+        assert_eq!(totals, vec![Totals(3), Totals(3)]);
+
+        assert_eq!(sections(input)[0].normalised(), "47|53\n97|13");
+        assert!(matches!(sections("a\nb")[0].normalised(), Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn test_records() {
+        let mut records = records("a  \r\n b\t\r\n  \r\n\r\nc\n\n\n");
+
+        let first = records.next().unwrap();
+        assert_eq!(first.first_line, 1);
+        assert_eq!(first.lines().collect::<Vec<_>>(), vec!["a", " b"]);
+
+        let second = records.next().unwrap();
+        assert_eq!((second.number, second.first_line, second.text), (2, 5, "c"));
+
+        assert_eq!(records.next(), None);
+    }
+
+    #[test]
+    fn test_parse_records() {
+        let totals: Vec<Totals> = parse_records("1\n2\n\n3\n4\n5").unwrap();
+        assert_eq!(totals, vec![Totals(3), Totals(12)]);
+
+        let error = parse_records::<Totals>("1\n\n2\nx").unwrap_err();
+        assert_eq!((error.section, error.line), (2, 3));
+    }
+
+    #[test]
+    fn test_parse_sections() {
+        let (rules, updates, last) = parse_sections(
+            EXAMPLE_INPUT,
+            (
+                |section: Section| {
+                    section.parse_lines(|line| {
+                        let (before, after) = line
+                            .split_once('|')
+                            .ok_or_else(|| "missing '|'".to_string())?;
+
+                        Ok::<_, String>((before.to_string(), after.to_string()))
+                    })
+                },
+                |section: Section| {
+                    section.parse_lines(|line| {
+                        line.split(',')
+                            .map(str::parse::<u64>)
+                            .collect::<Result<Vec<u64>, _>>()
+                    })
+                },
+                |section: Section| section.parse(str::parse::<u64>),
+            ),
+        )
+        .unwrap();
+
+        assert_eq!(rules[1], ("97".to_string(), "13".to_string()));
+        assert_eq!(updates[1], vec![97, 61, 53, 29, 13]);
+        assert_eq!(last, 1);
+    }
+
+    #[test]
+    fn test_parse_sections_errors() {
+        let error = parse_sections(
+            EXAMPLE_INPUT,
+            (
+                |section: Section| section.parse_lines(|line| Ok::<_, String>(line.len())),
+                |section: Section| section.parse_lines(str::parse::<u8>),
+                |section: Section| section.parse(str::parse::<u64>),
+            ),
+        )
+        .unwrap_err();
+
+        assert_eq!((error.section, error.line), (2, 4));
+
+        let error = parse_sections(
+            EXAMPLE_INPUT,
+            (|section: Section| section.parse(|text| Ok::<_, String>(text.len())),),
+        )
+        .unwrap_err();
+
+        assert_eq!(error.message, "expected 1 sections, found 3");
+    }
+}
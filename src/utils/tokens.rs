@@ -0,0 +1,169 @@
+use regex::Regex;
+
+enum Matcher {
+    Literal(String),
+    Pattern(Regex),
+}
+
+impl Matcher {
+    /// The length of this token if it matches at the very start of `text`.
+    fn match_len(&self, text: &str) -> Option<usize> {
+        let len = match self {
+            Matcher::Literal(literal) => {
+                text.starts_with(literal.as_str()).then_some(literal.len())
+            }
+            Matcher::Pattern(regex) => regex.find(text).map(|found| found.end()),
+        }?;
+
+        (len > 0).then_some(len)
+    }
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub enum TokenMode {
+    /// Report every token that matches at every position, so `eightwo` yields
+    /// both `eight` and `two`.
+    Overlapping,
+    /// At each position take the longest matching token, then continue after
+    /// it. Ties go to the token registered first.
+    LeftmostLongest,
+}
+
+#[derive(PartialEq, Eq, Debug, Clone, Copy)]
+pub struct TokenMatch<'a, K> {
+    pub kind: K,
+    /// Byte offset of the match within the scanned input.
+    pub start: usize,
+    pub text: &'a str,
+}
+
+/// Finds a set of literal or regex tokens in some input, each tagged with a
+/// `kind` supplied when the token is registered.
+pub struct TokenScanner<K> {
+    tokens: Vec<(Matcher, K)>,
+}
+
+impl<K: Copy> Default for TokenScanner<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Copy> TokenScanner<K> {
+    pub fn new() -> Self {
+        Self { tokens: vec![] }
+    }
+
+    pub fn literal(mut self, literal: &str, kind: K) -> Self {
+        self.tokens
+            .push((Matcher::Literal(literal.to_string()), kind));
+        self
+    }
+
+    /// Adds a regex token. Panics if `pattern` is not a valid regex.
+    pub fn pattern(mut self, pattern: &str, kind: K) -> Self {
+        let regex = Regex::new(&format!("^(?:{pattern})"))
+            .unwrap_or_else(|error| panic!("Invalid token pattern '{pattern}': {error}"));
+
+        self.tokens.push((Matcher::Pattern(regex), kind));
+        self
+    }
+
+    pub fn scan<'a>(&self, input: &'a str, mode: TokenMode) -> Vec<TokenMatch<'a, K>> {
+        let mut matches = vec![];
+        let mut position = 0;
+
+        while position < input.len() {
+            let rest = &input[position..];
+            let found = self
+                .tokens
+                .iter()
+                .filter_map(|(matcher, kind)| matcher.match_len(rest).map(|len| (len, *kind)));
+
+            let mut next = position + rest.chars().next().map_or(1, char::len_utf8);
+
+            match mode {
+                TokenMode::Overlapping => {
+                    matches.extend(found.map(|(len, kind)| TokenMatch {
+                        kind,
+                        start: position,
+                        text: &rest[..len],
+                    }));
+                }
+                TokenMode::LeftmostLongest => {
+                    let longest =
+                        found.fold(
+                            None,
+                            |longest: Option<(usize, K)>, (len, kind)| match longest {
+                                Some((longest_len, _)) if longest_len >= len => longest,
+                                _ => Some((len, kind)),
+                            },
+                        );
+
+                    if let Some((len, kind)) = longest {
+                        matches.push(TokenMatch {
+                            kind,
+                            start: position,
+                            text: &rest[..len],
+                        });
+                        next = position + len;
+                    }
+                }
+            }
+
+            position = next;
+        }
+
+        matches
+    }
+}
+
+mod tests {
+    #![allow(unused)]
+    use super::*;
+
+    fn digits() -> TokenScanner<u32> {
+        let words = [
+            "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+        ];
+
+        words
+            .iter()
+            .zip(1..)
+            .fold(TokenScanner::new(), |scanner, (word, value)| {
+                scanner.literal(word, value)
+            })
+            .pattern("[1-9]", 0)
+    }
+
+    #[test]
+    fn test_overlapping() {
+        let found = digits().scan("eightwo3", TokenMode::Overlapping);
+        let kinds: Vec<u32> = found.iter().map(|token| token.kind).collect();
+
+        assert_eq!(kinds, vec![8, 2, 0]);
+        assert_eq!((found[1].start, found[1].text), (4, "two"));
+        assert_eq!(found[2].text, "3");
+    }
+
+    #[test]
+    fn test_leftmost_longest() {
+        let scanner = TokenScanner::new()
+            .literal("do()", 'd')
+            .literal("don't()", 'n')
+            .literal("do", 'x')
+            .pattern(r"mul\(\d{1,3},\d{1,3}\)", 'm');
+
+        let found = scanner.scan(
+            "xmul(2,4)&don't()_mul(5,5)+do()?mul(8,5))",
+            TokenMode::LeftmostLongest,
+        );
+        let kinds: String = found.iter().map(|token| token.kind).collect();
+
+        assert_eq!(kinds, "mnmdm");
+        assert_eq!(found[2].text, "mul(5,5)");
+
+        let found = digits().scan("eightwo", TokenMode::LeftmostLongest);
+        assert_eq!(found.len(), 1);
+    }
+}